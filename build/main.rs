@@ -52,7 +52,10 @@ const WHITELISTED_FUNCS: &[&str] = &[
     "ext_adi_gyro_get",
     "ext_adi_gyro_reset",
     "ext_adi_gyro_shutdown",
+    "ext_adi_port_get_config",
+    "ext_adi_port_get_value",
     "ext_adi_port_set_config",
+    "ext_adi_port_set_value",
     "ext_adi_ultrasonic_get",
     "ext_adi_ultrasonic_init",
     "ext_adi_ultrasonic_shutdown",
@@ -116,6 +119,7 @@ const WHITELISTED_FUNCS: &[&str] = &[
     "motor_set_zero_position",
     "motor_tare_position",
     "mutex_delete",
+    "mutex_get_owner",
     "mutex_recursive_create",
     "mutex_recursive_give",
     "mutex_recursive_take",
@@ -129,6 +133,7 @@ const WHITELISTED_FUNCS: &[&str] = &[
     "rotation_reverse",
     "rotation_set_position",
     "rotation_set_reversed",
+    "sem_binary_create",
     "sem_create",
     "sem_delete",
     "sem_get_count",
@@ -148,12 +153,16 @@ const WHITELISTED_FUNCS: &[&str] = &[
     "task_delay",
     "task_delete",
     "task_get_by_name",
+    "task_get_count",
     "task_get_current",
     "task_get_name",
     "task_get_priority",
     "task_get_state",
     "task_notify",
     "task_notify_take",
+    "task_resume",
+    "task_set_priority",
+    "task_suspend",
     "controller_get_digital_new_press",
 ];
 
@@ -164,6 +173,7 @@ const WHITELISTED_VARS: &[&str] = &[
     "PROS_ERR_F_",
     "TASK_PRIORITY_DEFAULT",
     "TASK_PRIORITY_MAX",
+    "TASK_PRIORITY_MIN",
     "TASK_STACK_DEPTH_DEFAULT",
 ];
 
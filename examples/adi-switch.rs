@@ -0,0 +1,35 @@
+#![no_std]
+#![no_main]
+
+use core::time::Duration;
+use vex_rt::prelude::*;
+
+struct SwitchBot {
+    limit: AdiLimitSwitch,
+}
+
+impl Robot for SwitchBot {
+    fn new(peripherals: Peripherals) -> Self {
+        Self {
+            limit: peripherals
+                .port_a
+                .into_adi_switch(Duration::from_millis(20))
+                .unwrap(),
+        }
+    }
+    fn opcontrol(&'static self, ctx: Context) {
+        loop {
+            select! {
+                edge = self.limit.edge() => println!(
+                    "{:?} (presses: {}, releases: {})",
+                    edge,
+                    self.limit.press_count(),
+                    self.limit.release_count()
+                ),
+                _ = ctx.done() => break,
+            }
+        }
+    }
+}
+
+entry!(SwitchBot);
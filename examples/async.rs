@@ -0,0 +1,37 @@
+#![no_std]
+#![no_main]
+
+use core::time::Duration;
+use vex_rt::prelude::*;
+
+struct AsyncBot;
+
+async fn countdown(n: u32) -> u32 {
+    for i in (1..=n).rev() {
+        println!("{}", i);
+        selectable_future(delay(Duration::from_secs(1))).await;
+    }
+    n
+}
+
+impl Robot for AsyncBot {
+    fn new(_peripherals: Peripherals) -> Self {
+        AsyncBot
+    }
+    fn autonomous(&'static self, ctx: Context) {
+        println!("autonomous");
+        let executor = Executor::new();
+        let background = executor.spawn_local(countdown(10));
+        let total = executor.block_on(async {
+            let n = countdown(3).await;
+            n + *selectable_future(background.done()).await
+        });
+        println!("total = {}", total);
+        select! {
+            _ = ctx.done() => {},
+            _ = select_future(countdown(5)) => {},
+        }
+    }
+}
+
+entry!(AsyncBot);
@@ -0,0 +1,26 @@
+#![no_std]
+#![no_main]
+
+use core::time::Duration;
+use vex_rt::prelude::*;
+
+struct TimerBot;
+
+impl Robot for TimerBot {
+    fn new(_peripherals: Peripherals) -> Self {
+        TimerBot
+    }
+    fn opcontrol(&'static self, ctx: Context) {
+        println!("opcontrol");
+        let heartbeat = Timer::periodic_with(Duration::from_secs(1), || println!("tick")).unwrap();
+        heartbeat.start();
+        let timeout = Timer::one_shot(Duration::from_secs(5)).unwrap();
+        timeout.start();
+        select! {
+            _ = timeout.fired() => println!("timed out"),
+            _ = ctx.done() => println!("cancelled"),
+        }
+    }
+}
+
+entry!(TimerBot);
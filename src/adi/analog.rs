@@ -1,5 +1,8 @@
 //! # ADI Analog API.
 
+use core::time::Duration;
+
+use super::AdiAnalogSampler;
 use crate::bindings;
 use crate::error::{get_errno, Error};
 
@@ -109,6 +112,19 @@ impl AdiAnalog {
             x => Ok(x),
         }
     }
+
+    #[inline]
+    /// Moves the analog input into an [`AdiAnalogSampler`], which samples it in
+    /// the background and provides filtered readings; see
+    /// [`AdiAnalogSampler::new()`].
+    pub fn into_sampler(
+        self,
+        period: Duration,
+        window: usize,
+        alpha: f64,
+    ) -> Result<AdiAnalogSampler, Error> {
+        AdiAnalogSampler::new(self, period, window, alpha)
+    }
 }
 
 /// Represents possible errors for ADI analog operations.
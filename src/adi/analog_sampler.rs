@@ -0,0 +1,160 @@
+//! # ADI Analog Sampler API.
+
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use core::time::Duration;
+
+use super::AdiAnalog;
+use crate::{
+    error::Error,
+    rtos::{Context, Loop, Mutex, Task},
+    select,
+};
+
+/// An ADI analog input which is sampled at a fixed rate by a background task,
+/// providing filtered readings.
+///
+/// The most recent samples are kept in a window, from which a moving average
+/// and median are computed; an exponential moving average is also maintained
+/// over all samples. This gives stable readings from noisy sensors such as
+/// potentiometers, without callers needing to implement their own filtering.
+///
+/// The background task is stopped when the sampler is dropped.
+pub struct AdiAnalogSampler {
+    data: Arc<Mutex<SamplerData>>,
+    ctx: Context,
+}
+
+impl AdiAnalogSampler {
+    /// Creates a new sampler which reads the given analog input once per
+    /// `period`, keeping a window of `window` samples and applying an
+    /// exponential filter with weight `alpha` (from 0 to 1) for each new
+    /// sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window is empty or `alpha` is not within `(0, 1]`.
+    pub fn new(
+        analog: AdiAnalog,
+        period: Duration,
+        window: usize,
+        alpha: f64,
+    ) -> Result<Self, Error> {
+        assert!(window > 0, "sampling window must be non-empty");
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "filter weight must be within (0, 1], got {}",
+            alpha
+        );
+
+        let data = Arc::new(Mutex::try_new(SamplerData {
+            samples: VecDeque::with_capacity(window),
+            window,
+            alpha,
+            filtered: None,
+            count: 0,
+        })?);
+        let ctx = Context::new_global();
+
+        let task_data = data.clone();
+        let task_ctx = ctx.clone();
+        Task::spawn_ext(
+            "adi-analog-sampler",
+            Task::DEFAULT_PRIORITY,
+            Task::DEFAULT_STACK_DEPTH,
+            move || {
+                let mut l = Loop::new(period);
+                loop {
+                    // Samples which fail to read are skipped; the port is
+                    // already known to be a valid analog input.
+                    if let Ok(value) = analog.read() {
+                        task_data.lock().push(value);
+                    }
+                    select! {
+                        _ = l.select() => continue,
+                        _ = task_ctx.done() => break,
+                    }
+                }
+            },
+        )?;
+
+        Ok(Self { data, ctx })
+    }
+
+    #[inline]
+    /// Gets the most recent sample, if any.
+    pub fn latest(&self) -> Option<i32> {
+        self.data.lock().samples.back().copied()
+    }
+
+    /// Gets the mean of the samples in the window, if any.
+    pub fn average(&self) -> Option<f64> {
+        let lock = self.data.lock();
+        if lock.samples.is_empty() {
+            None
+        } else {
+            Some(lock.samples.iter().map(|&v| v as f64).sum::<f64>() / lock.samples.len() as f64)
+        }
+    }
+
+    /// Gets the median of the samples in the window, if any. This is robust
+    /// against occasional outliers.
+    pub fn median(&self) -> Option<f64> {
+        let mut samples: Vec<i32> = self.data.lock().samples.iter().copied().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        Some(if samples.len() % 2 == 0 {
+            (samples[mid - 1] as f64 + samples[mid] as f64) / 2.0
+        } else {
+            samples[mid] as f64
+        })
+    }
+
+    #[inline]
+    /// Gets the exponentially filtered value, if any samples have been taken.
+    pub fn filtered(&self) -> Option<f64> {
+        self.data.lock().filtered
+    }
+
+    #[inline]
+    /// Gets the total number of samples taken since the sampler was created.
+    pub fn sample_count(&self) -> u64 {
+        self.data.lock().count
+    }
+
+    /// Discards all samples, restarting the window and the exponential filter.
+    pub fn reset(&self) {
+        let mut lock = self.data.lock();
+        lock.samples.clear();
+        lock.filtered = None;
+    }
+}
+
+impl Drop for AdiAnalogSampler {
+    fn drop(&mut self) {
+        self.ctx.cancel();
+    }
+}
+
+struct SamplerData {
+    samples: VecDeque<i32>,
+    window: usize,
+    alpha: f64,
+    filtered: Option<f64>,
+    count: u64,
+}
+
+impl SamplerData {
+    fn push(&mut self, value: i32) {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+        self.filtered = Some(self.filtered.map_or(value as f64, |f| {
+            self.alpha * value as f64 + (1.0 - self.alpha) * f
+        }));
+        self.count += 1;
+    }
+}
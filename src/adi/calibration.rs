@@ -0,0 +1,130 @@
+//! Shared calibration logic for reflectance-based ADI analog sensors.
+
+use core::time::Duration;
+
+use super::{AdiAnalog, AdiAnalogError};
+use crate::{error::Error, rtos::Task};
+
+/// The number of samples averaged when calibrating the light level; this
+/// matches the behaviour of [`AdiAnalog::calibrate()`].
+const LIGHT_CALIBRATION_SAMPLES: i32 = 500;
+
+/// The delay between samples when calibrating the light level.
+const LIGHT_CALIBRATION_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Maps raw analog readings onto a normalized scale where 0.0 is the
+/// calibrated dark level and 1.0 is the calibrated light level, and tracks a
+/// thresholded state with hysteresis.
+pub(super) struct Calibration {
+    baseline: i32,
+    dark: f64,
+    light: f64,
+    low: f64,
+    high: f64,
+    state: bool,
+}
+
+impl Calibration {
+    pub(super) fn new() -> Self {
+        Self {
+            baseline: 0,
+            dark: 4095.0,
+            light: 0.0,
+            low: 0.4,
+            high: 0.6,
+            state: false,
+        }
+    }
+
+    pub(super) fn calibrate_dark(
+        &mut self,
+        analog: &mut AdiAnalog,
+    ) -> Result<i32, CalibrationError> {
+        // The baseline must track the analog input's own calibration even if
+        // the new level is rejected, since it converts readings back to raw.
+        self.baseline = analog.calibrate()?;
+        let dark = self.baseline;
+        if dark as f64 == self.light {
+            return Err(CalibrationError::LevelsEqual);
+        }
+        self.dark = dark as f64;
+        Ok(dark)
+    }
+
+    pub(super) fn calibrate_light(&mut self, analog: &AdiAnalog) -> Result<i32, CalibrationError> {
+        let mut total = 0;
+        for _ in 0..LIGHT_CALIBRATION_SAMPLES {
+            total += analog.read_calibrated()?;
+            Task::delay(LIGHT_CALIBRATION_INTERVAL);
+        }
+        let light = total / LIGHT_CALIBRATION_SAMPLES + self.baseline;
+        if light as f64 == self.dark {
+            return Err(CalibrationError::LevelsEqual);
+        }
+        self.light = light as f64;
+        Ok(light)
+    }
+
+    pub(super) fn set_levels(&mut self, dark: i32, light: i32) {
+        assert!(dark != light, "dark and light levels must differ");
+        self.dark = dark as f64;
+        self.light = light as f64;
+    }
+
+    pub(super) fn levels(&self) -> (i32, i32) {
+        (self.dark as i32, self.light as i32)
+    }
+
+    pub(super) fn set_thresholds(&mut self, low: f64, high: f64) {
+        assert!(
+            (0.0..=1.0).contains(&low) && (0.0..=1.0).contains(&high) && low <= high,
+            "invalid thresholds: low = {}, high = {}",
+            low,
+            high
+        );
+        self.low = low;
+        self.high = high;
+    }
+
+    pub(super) fn normalized(&self, analog: &AdiAnalog) -> Result<f64, AdiAnalogError> {
+        let raw = (analog.read_calibrated()? + self.baseline) as f64;
+        Ok(((raw - self.dark) / (self.light - self.dark)).clamp(0.0, 1.0))
+    }
+
+    pub(super) fn update(&mut self, value: f64) -> bool {
+        if self.state && value < self.low {
+            self.state = false;
+        } else if !self.state && value > self.high {
+            self.state = true;
+        }
+        self.state
+    }
+}
+
+/// Represents possible errors for calibrating the dark and light levels of a
+/// reflectance sensor.
+#[derive(Debug)]
+pub enum CalibrationError {
+    /// Reading the analog input failed.
+    Analog(AdiAnalogError),
+    /// The calibrated dark and light levels are equal, so readings cannot be
+    /// normalized between them.
+    LevelsEqual,
+}
+
+impl From<AdiAnalogError> for CalibrationError {
+    fn from(err: AdiAnalogError) -> Self {
+        Self::Analog(err)
+    }
+}
+
+impl From<CalibrationError> for Error {
+    fn from(err: CalibrationError) -> Self {
+        match err {
+            CalibrationError::Analog(err) => err.into(),
+            CalibrationError::LevelsEqual => {
+                Error::Custom("dark and light levels are equal".into())
+            }
+        }
+    }
+}
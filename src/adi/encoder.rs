@@ -1,7 +1,14 @@
 //! # ADI Encoder API.
 
+use alloc::collections::VecDeque;
+use core::f64::consts::PI;
+
 use crate::bindings;
 use crate::error::{get_errno, Error};
+use crate::rtos::{time_since_start, Instant};
+
+/// The number of encoder ticks in one revolution of an ADI encoder.
+pub const ADI_ENCODER_TICKS_PER_REV: f64 = 360.0;
 
 /// A struct which represents a V5 ADI port configured as an ADI encoder.
 pub struct AdiEncoder {
@@ -46,6 +53,13 @@ impl AdiEncoder {
             x => Ok(x),
         }
     }
+
+    #[inline]
+    /// Wraps the encoder in an [`AdiEncoderTracker`] which estimates its
+    /// velocity using the given method.
+    pub fn into_tracker(self, estimation: VelocityEstimation) -> AdiEncoderTracker {
+        AdiEncoderTracker::new(self, estimation)
+    }
 }
 
 impl Drop for AdiEncoder {
@@ -94,3 +108,179 @@ impl From<AdiEncoderError> for Error {
         }
     }
 }
+
+/// Represents a method of estimating velocity from timestamped encoder samples.
+#[derive(Clone, Copy, Debug)]
+pub enum VelocityEstimation {
+    /// The velocity is computed as the average over the given number of most
+    /// recent sample intervals. Larger windows give smoother but more delayed
+    /// estimates.
+    Window(usize),
+    /// The velocity between consecutive samples is smoothed with an
+    /// exponential moving average, using the given weight (from 0 to 1) for
+    /// each new sample. Smaller weights give smoother but more delayed
+    /// estimates.
+    Filter(f64),
+}
+
+/// A wrapper around an [`AdiEncoder`] which records timestamped samples in
+/// order to estimate the velocity of the encoder, and optionally converts
+/// ticks to linear distance travelled by a wheel.
+///
+/// [`AdiEncoderTracker::update()`] must be called periodically (e.g., once per
+/// iteration of a control loop) in order to record new samples.
+pub struct AdiEncoderTracker {
+    encoder: AdiEncoder,
+    estimation: VelocityEstimation,
+    samples: VecDeque<(Instant, i32)>,
+    velocity: f64,
+    wheel_diameter: Option<f64>,
+}
+
+impl AdiEncoderTracker {
+    /// Creates a new tracker wrapping the given encoder.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the estimation window is zero or the filter weight is not
+    /// within `(0, 1]`.
+    pub fn new(encoder: AdiEncoder, estimation: VelocityEstimation) -> Self {
+        match estimation {
+            VelocityEstimation::Window(n) => {
+                assert!(n > 0, "velocity estimation window must be non-empty")
+            }
+            VelocityEstimation::Filter(alpha) => assert!(
+                alpha > 0.0 && alpha <= 1.0,
+                "velocity filter weight must be within (0, 1], got {}",
+                alpha
+            ),
+        }
+        Self {
+            encoder,
+            estimation,
+            samples: VecDeque::new(),
+            velocity: 0.0,
+            wheel_diameter: None,
+        }
+    }
+
+    #[inline]
+    /// Sets the diameter of the wheel attached to the encoder, enabling the
+    /// [`AdiEncoderTracker::distance()`] and
+    /// [`AdiEncoderTracker::linear_velocity()`] methods. The results of those
+    /// methods are in the same units as the diameter.
+    pub fn with_wheel_diameter(mut self, diameter: f64) -> Self {
+        self.wheel_diameter = Some(diameter);
+        self
+    }
+
+    /// Samples the encoder, updating the velocity estimate. Returns the
+    /// current number of ticks.
+    pub fn update(&mut self) -> Result<i32, AdiEncoderError> {
+        let ticks = self.encoder.get()?;
+        let now = time_since_start();
+
+        if let Some(&(last_time, last_ticks)) = self.samples.back() {
+            if now <= last_time {
+                // No time has passed; there is nothing new to learn.
+                return Ok(ticks);
+            }
+            match self.estimation {
+                VelocityEstimation::Window(n) => {
+                    self.samples.push_back((now, ticks));
+                    while self.samples.len() > n + 1 {
+                        self.samples.pop_front();
+                    }
+                    let (first_time, first_ticks) = self.samples[0];
+                    self.velocity = rate(first_time, first_ticks, now, ticks);
+                }
+                VelocityEstimation::Filter(alpha) => {
+                    let instant = rate(last_time, last_ticks, now, ticks);
+                    self.velocity = if self.samples.len() > 1 {
+                        alpha * instant + (1.0 - alpha) * self.velocity
+                    } else {
+                        instant
+                    };
+                    self.samples.clear();
+                    self.samples.push_back((last_time, last_ticks));
+                    self.samples.push_back((now, ticks));
+                }
+            }
+        } else {
+            self.samples.push_back((now, ticks));
+        }
+
+        Ok(ticks)
+    }
+
+    /// Resets the encoder to zero and discards all recorded samples.
+    pub fn reset(&mut self) -> Result<(), AdiEncoderError> {
+        self.encoder.reset()?;
+        self.samples.clear();
+        self.velocity = 0.0;
+        Ok(())
+    }
+
+    #[inline]
+    /// Gets the number of ticks as of the most recent sample, if any.
+    pub fn ticks(&self) -> Option<i32> {
+        self.samples.back().map(|&(_, ticks)| ticks)
+    }
+
+    #[inline]
+    /// Gets the timestamp of the most recent sample, if any.
+    pub fn last_update(&self) -> Option<Instant> {
+        self.samples.back().map(|&(time, _)| time)
+    }
+
+    #[inline]
+    /// Gets the estimated velocity in ticks per second. This is zero until at
+    /// least two samples have been recorded.
+    pub fn velocity(&self) -> f64 {
+        self.velocity
+    }
+
+    #[inline]
+    /// Gets the estimated velocity in revolutions per minute.
+    pub fn rpm(&self) -> f64 {
+        self.velocity * 60.0 / ADI_ENCODER_TICKS_PER_REV
+    }
+
+    #[inline]
+    /// Gets the distance travelled by the wheel as of the most recent sample,
+    /// if a wheel diameter has been set.
+    pub fn distance(&self) -> Option<f64> {
+        self.ticks_to_distance(self.ticks()? as f64)
+    }
+
+    #[inline]
+    /// Gets the estimated linear velocity of the wheel per second, if a wheel
+    /// diameter has been set.
+    pub fn linear_velocity(&self) -> Option<f64> {
+        self.ticks_to_distance(self.velocity)
+    }
+
+    #[inline]
+    /// Converts a number of ticks to the distance travelled by the wheel, if a
+    /// wheel diameter has been set.
+    pub fn ticks_to_distance(&self, ticks: f64) -> Option<f64> {
+        Some(ticks * PI * self.wheel_diameter? / ADI_ENCODER_TICKS_PER_REV)
+    }
+
+    #[inline]
+    /// Gets a reference to the underlying encoder.
+    pub fn encoder(&self) -> &AdiEncoder {
+        &self.encoder
+    }
+
+    #[inline]
+    /// Unwraps the underlying encoder.
+    pub fn into_inner(self) -> AdiEncoder {
+        self.encoder
+    }
+}
+
+#[inline]
+fn rate(t0: Instant, ticks0: i32, t1: Instant, ticks1: i32) -> f64 {
+    (ticks1 - ticks0) as f64 / (t1 - t0).as_secs_f64()
+}
@@ -0,0 +1,93 @@
+//! # ADI Light Sensor API.
+
+use super::{
+    calibration::{Calibration, CalibrationError},
+    AdiAnalog, AdiAnalogError,
+};
+
+/// A struct which represents a V5 ADI port configured as a light sensor.
+///
+/// Readings are normalized between the dark level (0.0) and the light level
+/// (1.0), which should be calibrated by exposing the sensor to each lighting
+/// condition in turn and calling [`AdiLightSensor::calibrate_dark()`] and
+/// [`AdiLightSensor::calibrate_light()`].
+pub struct AdiLightSensor {
+    analog: AdiAnalog,
+    calibration: Calibration,
+}
+
+impl AdiLightSensor {
+    /// Creates a new light sensor from an analog input.
+    pub fn new(analog: AdiAnalog) -> Self {
+        Self {
+            analog,
+            calibration: Calibration::new(),
+        }
+    }
+
+    /// Calibrates the dark level. This takes approximately 0.5 s.
+    ///
+    /// Returns: The raw dark level, from 0 to 4095. Fails with
+    /// [`CalibrationError::LevelsEqual`], leaving the dark level unchanged, if
+    /// it is equal to the light level.
+    pub fn calibrate_dark(&mut self) -> Result<i32, CalibrationError> {
+        self.calibration.calibrate_dark(&mut self.analog)
+    }
+
+    /// Calibrates the light level. This takes approximately 0.5 s.
+    ///
+    /// Returns: The raw light level, from 0 to 4095. Fails with
+    /// [`CalibrationError::LevelsEqual`], leaving the light level unchanged, if
+    /// it is equal to the dark level.
+    pub fn calibrate_light(&mut self) -> Result<i32, CalibrationError> {
+        self.calibration.calibrate_light(&self.analog)
+    }
+
+    #[inline]
+    /// Sets the raw dark and light levels directly, e.g. from a previous
+    /// calibration. Panics if the levels are equal.
+    pub fn set_calibration(&mut self, dark: i32, light: i32) {
+        self.calibration.set_levels(dark, light)
+    }
+
+    #[inline]
+    /// Gets the raw dark and light levels, in that order.
+    pub fn calibration(&self) -> (i32, i32) {
+        self.calibration.levels()
+    }
+
+    #[inline]
+    /// Sets the normalized thresholds for [`AdiLightSensor::is_light()`]. Light
+    /// is detected once the reading rises above `high`, and lost once it
+    /// falls back below `low`. Panics unless `0.0 <= low <= high <= 1.0`.
+    pub fn set_thresholds(&mut self, low: f64, high: f64) {
+        self.calibration.set_thresholds(low, high)
+    }
+
+    #[inline]
+    /// Reads the raw value of the sensor, from 0 to 4095. Lower values
+    /// indicate brighter light.
+    pub fn read(&self) -> Result<i32, AdiAnalogError> {
+        self.analog.read()
+    }
+
+    #[inline]
+    /// Reads the sensor on a normalized scale from 0.0 (the dark level) to 1.0
+    /// (the light level).
+    pub fn normalized(&self) -> Result<f64, AdiAnalogError> {
+        self.calibration.normalized(&self.analog)
+    }
+
+    /// Checks whether the sensor is exposed to light, with hysteresis to avoid
+    /// flickering near the threshold.
+    pub fn is_light(&mut self) -> Result<bool, AdiAnalogError> {
+        let value = self.normalized()?;
+        Ok(self.calibration.update(value))
+    }
+
+    #[inline]
+    /// Unwraps the underlying analog input.
+    pub fn into_inner(self) -> AdiAnalog {
+        self.analog
+    }
+}
@@ -1,19 +1,31 @@
 //! Interfacing with ADI components of the Vex V5 robot.
 
 mod analog;
+mod analog_sampler;
+mod calibration;
 mod digital_in;
 mod digital_out;
 mod encoder;
 mod expander;
 mod gyro;
+mod light_sensor;
+mod line_tracker;
 mod port;
+mod raw;
+mod switch;
 mod ultrasonic;
 
 pub use analog::*;
+pub use analog_sampler::*;
+pub use calibration::CalibrationError;
 pub use digital_in::*;
 pub use digital_out::*;
 pub use encoder::*;
 pub use expander::*;
 pub use gyro::*;
+pub use light_sensor::*;
+pub use line_tracker::*;
 pub use port::*;
+pub use raw::*;
+pub use switch::*;
 pub use ultrasonic::*;
@@ -2,13 +2,15 @@
 
 use super::{
     AdiAnalog, AdiAnalogError, AdiDigitalInput, AdiDigitalInputError, AdiDigitalOutput,
-    AdiDigitalOutputError, AdiEncoder, AdiEncoderError, AdiGyro, AdiGyroError, AdiUltrasonic,
+    AdiDigitalOutputError, AdiEncoder, AdiEncoderError, AdiGyro, AdiGyroError, AdiLightSensor,
+    AdiLineTracker, AdiPortConfig, AdiRaw, AdiRawError, AdiSwitch, AdiUltrasonic,
     AdiUltrasonicError,
 };
 
-use crate::bindings;
+use crate::{bindings, error::Error};
 use core::cmp::Ordering;
 use core::convert::{TryFrom, TryInto};
+use core::time::Duration;
 
 /// A struct which represents an unconfigured ADI port.
 pub struct AdiPort {
@@ -47,6 +49,18 @@ impl AdiPort {
         self.try_into()
     }
 
+    /// Turns this port into an ADI line tracker.
+    #[inline]
+    pub fn into_adi_line_tracker(self) -> Result<AdiLineTracker, AdiAnalogError> {
+        Ok(AdiLineTracker::new(self.try_into()?))
+    }
+
+    /// Turns this port into an ADI light sensor.
+    #[inline]
+    pub fn into_adi_light_sensor(self) -> Result<AdiLightSensor, AdiAnalogError> {
+        Ok(AdiLightSensor::new(self.try_into()?))
+    }
+
     /// Turns this port into an ADI digital input.
     #[inline]
     pub fn into_adi_digital_input(self) -> Result<AdiDigitalInput, AdiDigitalInputError> {
@@ -75,6 +89,19 @@ impl AdiPort {
     pub fn into_adi_ultrasonic(self, bottom: Self) -> Result<AdiUltrasonic, AdiUltrasonicError> {
         (self, bottom).try_into()
     }
+
+    /// Turns this port into a raw ADI port with the given configuration.
+    #[inline]
+    pub fn into_adi_raw(self, config: AdiPortConfig) -> Result<AdiRaw, AdiRawError> {
+        (self, config).try_into()
+    }
+
+    /// Turns this port into a debounced ADI switch, such as a limit switch or
+    /// bumper.
+    #[inline]
+    pub fn into_adi_switch(self, debounce: Duration) -> Result<AdiSwitch, Error> {
+        AdiSwitch::new(self.try_into()?, debounce)
+    }
 }
 
 impl TryFrom<AdiPort> for AdiAnalog {
@@ -173,3 +200,18 @@ impl TryFrom<(AdiPort, AdiPort)> for AdiUltrasonic {
         }
     }
 }
+
+impl TryFrom<(AdiPort, AdiPortConfig)> for AdiRaw {
+    type Error = AdiRawError;
+
+    /// Converts an `(AdiPort, AdiPortConfig)` into an [`AdiRaw`].
+    fn try_from(port_config: (AdiPort, AdiPortConfig)) -> Result<Self, Self::Error> {
+        unsafe {
+            AdiRaw::new(
+                port_config.0.port,
+                port_config.0.expander_port,
+                port_config.1,
+            )
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! # ADI Raw Port API.
+
+use crate::bindings;
+use crate::error::{get_errno, Error};
+
+/// A struct which represents a V5 ADI port in an arbitrary configuration, with
+/// direct access to its raw value.
+///
+/// This is an escape hatch for three-wire devices which are not otherwise
+/// supported by this crate. The meaning of the raw value depends on the
+/// configured [`AdiPortConfig`].
+pub struct AdiRaw {
+    port: u8,
+    expander_port: u8,
+}
+
+impl AdiRaw {
+    /// Initializes an ADI port with the given configuration.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because it allows the user to create multiple
+    /// mutable references to the same ADI port. You likely want to implement
+    /// [`Robot::new()`](crate::robot::Robot::new()) instead.
+    pub unsafe fn new(
+        port: u8,
+        expander_port: u8,
+        config: AdiPortConfig,
+    ) -> Result<Self, AdiRawError> {
+        let mut raw = Self {
+            port,
+            expander_port,
+        };
+        raw.set_config(config)?;
+        Ok(raw)
+    }
+
+    /// Sets the configuration of the port.
+    pub fn set_config(&mut self, config: AdiPortConfig) -> Result<(), AdiRawError> {
+        match unsafe {
+            bindings::ext_adi_port_set_config(self.expander_port, self.port, config.into())
+        } {
+            bindings::PROS_ERR_ => Err(AdiRawError::from_errno()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Gets the current configuration of the port.
+    pub fn get_config(&self) -> Result<AdiPortConfig, AdiRawError> {
+        match unsafe { bindings::ext_adi_port_get_config(self.expander_port, self.port) } {
+            bindings::adi_port_config_e_E_ADI_ERR => Err(AdiRawError::from_errno()),
+            x => Ok(x.into()),
+        }
+    }
+
+    /// Reads the raw value of the port.
+    ///
+    /// The meaning of the value depends on the current configuration of the
+    /// port; for example, an analog input reads from 0 to 4095 and a digital
+    /// input reads 0 or 1.
+    pub fn read(&self) -> Result<i32, AdiRawError> {
+        match unsafe { bindings::ext_adi_port_get_value(self.expander_port, self.port) } {
+            bindings::PROS_ERR_ => Err(AdiRawError::from_errno()),
+            x => Ok(x),
+        }
+    }
+
+    /// Writes a raw value to the port.
+    ///
+    /// The meaning of the value depends on the current configuration of the
+    /// port; for example, a digital output accepts 0 or 1 and a legacy PWM
+    /// output accepts -127 to 127.
+    pub fn write(&mut self, value: i32) -> Result<(), AdiRawError> {
+        match unsafe { bindings::ext_adi_port_set_value(self.expander_port, self.port, value) } {
+            bindings::PROS_ERR_ => Err(AdiRawError::from_errno()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Represents the possible configurations of an ADI port.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdiPortConfig {
+    /// Analog input; also used by potentiometers, line trackers, light sensors
+    /// and accelerometers.
+    AnalogIn,
+    /// Analog output.
+    AnalogOut,
+    /// Digital input; also used by buttons, bumpers and limit switches.
+    DigitalIn,
+    /// Digital output.
+    DigitalOut,
+    /// Legacy yaw-rate gyro.
+    LegacyGyro,
+    /// Legacy servo.
+    LegacyServo,
+    /// Legacy PWM output (e.g. a motor controller).
+    LegacyPwm,
+    /// Legacy quadrature encoder.
+    LegacyEncoder,
+    /// Legacy ultrasonic sensor.
+    LegacyUltrasonic,
+    /// Undefined configuration.
+    Undefined,
+    /// Unrecognized value from PROS/vexOS.
+    Unknown(u32),
+}
+
+impl From<AdiPortConfig> for bindings::adi_port_config_e_t {
+    fn from(config: AdiPortConfig) -> Self {
+        match config {
+            AdiPortConfig::AnalogIn => bindings::adi_port_config_e_E_ADI_ANALOG_IN,
+            AdiPortConfig::AnalogOut => bindings::adi_port_config_e_E_ADI_ANALOG_OUT,
+            AdiPortConfig::DigitalIn => bindings::adi_port_config_e_E_ADI_DIGITAL_IN,
+            AdiPortConfig::DigitalOut => bindings::adi_port_config_e_E_ADI_DIGITAL_OUT,
+            AdiPortConfig::LegacyGyro => bindings::adi_port_config_e_E_ADI_LEGACY_GYRO,
+            AdiPortConfig::LegacyServo => bindings::adi_port_config_e_E_ADI_LEGACY_SERVO,
+            AdiPortConfig::LegacyPwm => bindings::adi_port_config_e_E_ADI_LEGACY_PWM,
+            AdiPortConfig::LegacyEncoder => bindings::adi_port_config_e_E_ADI_LEGACY_ENCODER,
+            AdiPortConfig::LegacyUltrasonic => bindings::adi_port_config_e_E_ADI_LEGACY_ULTRASONIC,
+            AdiPortConfig::Undefined => bindings::adi_port_config_e_E_ADI_TYPE_UNDEFINED,
+            AdiPortConfig::Unknown(x) => x,
+        }
+    }
+}
+
+impl From<bindings::adi_port_config_e_t> for AdiPortConfig {
+    fn from(config: bindings::adi_port_config_e_t) -> Self {
+        match config {
+            bindings::adi_port_config_e_E_ADI_ANALOG_IN => Self::AnalogIn,
+            bindings::adi_port_config_e_E_ADI_ANALOG_OUT => Self::AnalogOut,
+            bindings::adi_port_config_e_E_ADI_DIGITAL_IN => Self::DigitalIn,
+            bindings::adi_port_config_e_E_ADI_DIGITAL_OUT => Self::DigitalOut,
+            bindings::adi_port_config_e_E_ADI_LEGACY_GYRO => Self::LegacyGyro,
+            bindings::adi_port_config_e_E_ADI_LEGACY_SERVO => Self::LegacyServo,
+            bindings::adi_port_config_e_E_ADI_LEGACY_PWM => Self::LegacyPwm,
+            bindings::adi_port_config_e_E_ADI_LEGACY_ENCODER => Self::LegacyEncoder,
+            bindings::adi_port_config_e_E_ADI_LEGACY_ULTRASONIC => Self::LegacyUltrasonic,
+            bindings::adi_port_config_e_E_ADI_TYPE_UNDEFINED => Self::Undefined,
+            x => Self::Unknown(x),
+        }
+    }
+}
+
+/// Represents possible errors for raw ADI port operations.
+#[derive(Debug)]
+pub enum AdiRawError {
+    /// Port is out of range (1-8).
+    PortsOutOfRange,
+    /// Port is not configured in a mode which supports the operation.
+    PortsWrongConfig,
+    /// Unknown error.
+    Unknown(i32),
+}
+
+impl AdiRawError {
+    fn from_errno() -> Self {
+        match get_errno() {
+            libc::ENXIO => Self::PortsOutOfRange,
+            libc::EADDRINUSE => Self::PortsWrongConfig,
+            x => Self::Unknown(x),
+        }
+    }
+}
+
+impl From<AdiRawError> for Error {
+    fn from(err: AdiRawError) -> Self {
+        match err {
+            AdiRawError::PortsOutOfRange => Error::Custom("port is out of range".into()),
+            AdiRawError::PortsWrongConfig => {
+                Error::Custom("port is not configured for this operation".into())
+            }
+            AdiRawError::Unknown(n) => Error::System(n),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! # ADI Switch API.
+
+use alloc::sync::Arc;
+use core::time::Duration;
+use owner_monad::{Owner, OwnerMut};
+
+use super::AdiDigitalInput;
+use crate::{
+    error::Error,
+    rtos::{
+        handle_event, time_since_start, Context, Event, EventHandle, GenericSleep, Instant, Loop,
+        Mutex, Selectable, Task,
+    },
+    select,
+};
+
+/// The interval at which switches are sampled by their background task.
+const SAMPLE_PERIOD: Duration = Duration::from_millis(5);
+
+/// A debounced ADI digital input, such as a limit switch or bumper.
+///
+/// The input is sampled by a background task, which only accepts a change in
+/// state once the input has held the new value for the debounce duration. Each
+/// accepted change is an edge: a press (rising) or a release (falling). Edges
+/// are counted, and can be awaited via [`AdiSwitch::pressed()`],
+/// [`AdiSwitch::released()`] and [`AdiSwitch::edge()`].
+///
+/// The background task is stopped when the switch is dropped.
+pub struct AdiSwitch {
+    data: Arc<Mutex<SwitchData>>,
+    ctx: Context,
+}
+
+/// A limit switch; see [`AdiSwitch`].
+pub type AdiLimitSwitch = AdiSwitch;
+
+/// A bumper switch; see [`AdiSwitch`].
+pub type AdiBumper = AdiSwitch;
+
+impl AdiSwitch {
+    /// Creates a new debounced switch from a digital input, spawning its
+    /// background task.
+    pub fn new(input: AdiDigitalInput, debounce: Duration) -> Result<Self, Error> {
+        let state = input.read()?;
+        let data = Arc::new(Mutex::try_new(SwitchData {
+            state,
+            candidate: state,
+            since: time_since_start(),
+            initial: state,
+            presses: 0,
+            releases: 0,
+            reported: 0,
+            event: Event::new(),
+        })?);
+        let ctx = Context::new_global();
+
+        let task_data = data.clone();
+        let task_ctx = ctx.clone();
+        Task::spawn_ext(
+            "adi-switch",
+            Task::DEFAULT_PRIORITY,
+            Task::DEFAULT_STACK_DEPTH,
+            move || {
+                let mut l = Loop::new(SAMPLE_PERIOD);
+                loop {
+                    // Samples which fail to read are skipped; the port is
+                    // already known to be a valid digital input.
+                    if let Ok(value) = input.read() {
+                        task_data.lock().sample(value, debounce);
+                    }
+                    select! {
+                        _ = l.select() => continue,
+                        _ = task_ctx.done() => break,
+                    }
+                }
+            },
+        )?;
+
+        Ok(Self { data, ctx })
+    }
+
+    #[inline]
+    /// Gets the debounced state of the switch; `true` means pressed.
+    pub fn is_pressed(&self) -> bool {
+        self.data.lock().state
+    }
+
+    #[inline]
+    /// Gets the number of presses (rising edges) since the switch was created.
+    pub fn press_count(&self) -> u32 {
+        self.data.lock().presses
+    }
+
+    #[inline]
+    /// Gets the number of releases (falling edges) since the switch was
+    /// created.
+    pub fn release_count(&self) -> u32 {
+        self.data.lock().releases
+    }
+
+    /// A [`Selectable`] event which occurs when the switch is next pressed.
+    pub fn pressed(&'_ self) -> impl Selectable + '_ {
+        let presses = self.press_count();
+        self.select_edge(move |data| (data.presses != presses).then_some(()))
+    }
+
+    /// A [`Selectable`] event which occurs when the switch is next released.
+    pub fn released(&'_ self) -> impl Selectable + '_ {
+        let releases = self.release_count();
+        self.select_edge(move |data| (data.releases != releases).then_some(()))
+    }
+
+    /// A [`Selectable`] event which occurs when there is an edge which has not
+    /// yet been reported, and reports the oldest such edge.
+    ///
+    /// Edges are reported from the creation of the switch, so none are lost if
+    /// the switch is pressed and released again before this event is polled.
+    pub fn edge(&'_ self) -> impl Selectable<SwitchEdge> + '_ {
+        self.select_edge(|data| {
+            if data.reported == data.edges() {
+                return None;
+            }
+            // Edges alternate, starting with the opposite of the initial state.
+            let edge = if (data.reported % 2 == 0) != data.initial {
+                SwitchEdge::Pressed
+            } else {
+                SwitchEdge::Released
+            };
+            data.reported = data.reported.wrapping_add(1);
+            Some(edge)
+        })
+    }
+
+    fn select_edge<'a, T: 'a>(
+        &'a self,
+        check: impl Fn(&mut SwitchData) -> Option<T> + 'a,
+    ) -> impl Selectable<T> + 'a {
+        struct EdgeSelect<'b, T, F: Fn(&mut SwitchData) -> Option<T>> {
+            check: F,
+            handle: EventHandle<&'b Mutex<SwitchData>>,
+        }
+
+        impl<'b, T, F: Fn(&mut SwitchData) -> Option<T>> Selectable<T> for EdgeSelect<'b, T, F> {
+            fn poll(self) -> Result<T, Self> {
+                self.handle
+                    .with(|mtx| (self.check)(&mut mtx.lock()))
+                    .flatten()
+                    .ok_or(self)
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                GenericSleep::NotifyTake(None)
+            }
+        }
+
+        EdgeSelect {
+            check,
+            handle: handle_event(&*self.data),
+        }
+    }
+}
+
+impl Drop for AdiSwitch {
+    fn drop(&mut self) {
+        self.ctx.cancel();
+    }
+}
+
+/// Represents an edge of an [`AdiSwitch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwitchEdge {
+    /// The switch was pressed (rising edge).
+    Pressed,
+    /// The switch was released (falling edge).
+    Released,
+}
+
+struct SwitchData {
+    state: bool,
+    candidate: bool,
+    since: Instant,
+    /// The debounced state when the switch was created.
+    initial: bool,
+    presses: u32,
+    releases: u32,
+    /// The number of edges reported by [`AdiSwitch::edge()`].
+    reported: u32,
+    event: Event,
+}
+
+impl SwitchData {
+    #[inline]
+    fn edges(&self) -> u32 {
+        self.presses.wrapping_add(self.releases)
+    }
+
+    fn sample(&mut self, value: bool, debounce: Duration) {
+        let now = time_since_start();
+        if value != self.candidate {
+            self.candidate = value;
+            self.since = now;
+        }
+        if self.candidate != self.state && now - self.since >= debounce {
+            self.state = self.candidate;
+            if self.state {
+                self.presses = self.presses.wrapping_add(1);
+            } else {
+                self.releases = self.releases.wrapping_add(1);
+            }
+            self.event.notify();
+        }
+    }
+}
+
+impl OwnerMut<Event> for &Mutex<SwitchData> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
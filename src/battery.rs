@@ -3,6 +3,7 @@
 use crate::{
     bindings,
     error::{get_errno, Error},
+    units::{Current, Temperature, Voltage},
 };
 
 /// A struct which represents a V5 Battery
@@ -32,7 +33,7 @@ impl Battery {
     /// Gets the current temperature of the battery, as reported by VEXos
     pub fn get_temperature() -> Result<f64, BatteryError> {
         unsafe {
-            let x = bindings::battery_get_capacity();
+            let x = bindings::battery_get_temperature();
             if x == bindings::PROS_ERR_F_ {
                 Err(BatteryError::from_errno())
             } else {
@@ -48,6 +49,24 @@ impl Battery {
             x => Ok(x),
         }
     }
+
+    #[inline]
+    /// Gets the current drawn from the battery.
+    pub fn current() -> Result<Current, BatteryError> {
+        Ok(Current::from_milliamps(Self::get_current()? as f64))
+    }
+
+    #[inline]
+    /// Gets the temperature of the battery.
+    pub fn temperature() -> Result<Temperature, BatteryError> {
+        Ok(Temperature::from_celsius(Self::get_temperature()?))
+    }
+
+    #[inline]
+    /// Gets the voltage of the battery.
+    pub fn voltage() -> Result<Voltage, BatteryError> {
+        Ok(Voltage::from_millivolts(Self::get_voltage()? as f64))
+    }
 }
 
 /// Represents possible errors for battery operations.
@@ -3,11 +3,14 @@
 use crate::{
     bindings,
     error::{get_errno, Error},
+    smart_port::{DeviceType, PortMonitor},
+    units::{Length, Velocity},
 };
 
 /// A struct which represents a V5 smart port configured as a distance sensor.
 pub struct DistanceSensor {
     port: u8,
+    monitor: PortMonitor,
 }
 
 impl DistanceSensor {
@@ -19,7 +22,10 @@ impl DistanceSensor {
     /// mutable references to the same distance sensor. You likely want to
     /// implement [`Robot::new()`](crate::robot::Robot::new()) instead.
     pub unsafe fn new(port: u8) -> DistanceSensor {
-        DistanceSensor { port }
+        DistanceSensor {
+            port,
+            monitor: PortMonitor::new(port, DeviceType::Distance),
+        }
     }
 
     /// Gets the currently measured distance from the sensor in millimetres.
@@ -60,6 +66,47 @@ impl DistanceSensor {
             x => Ok(x),
         }
     }
+
+    #[inline]
+    /// Gets the currently measured distance from the sensor.
+    pub fn distance(&self) -> Result<Length, DistanceSensorError> {
+        Ok(Length::from_millimeters(self.get_distance()? as f64))
+    }
+
+    #[inline]
+    /// Gets the object velocity.
+    pub fn object_velocity(&self) -> Result<Velocity, DistanceSensorError> {
+        Ok(Velocity::from_meters_per_second(
+            self.get_object_velocity()?,
+        ))
+    }
+
+    /// Checks whether the sensor has been plugged back in since the last
+    /// check. Returns `true` if the sensor was reconnected.
+    ///
+    /// The sensor has no configuration to re-apply, so this is never called
+    /// automatically.
+    pub fn check_connection(&self) -> bool {
+        let reconnected = self.monitor.poll();
+        if reconnected {
+            self.monitor.mark_configured();
+        }
+        reconnected
+    }
+
+    #[inline]
+    /// Checks whether the sensor was connected as of the last call to
+    /// [`DistanceSensor::check_connection()`].
+    pub fn is_connected(&self) -> bool {
+        self.monitor.is_connected()
+    }
+
+    #[inline]
+    /// Gets the number of times the sensor has been unplugged and plugged back
+    /// in since it was constructed.
+    pub fn reconnect_count(&self) -> u32 {
+        self.monitor.reconnects()
+    }
 }
 
 /// Represents possible errors for distance sensor operations.
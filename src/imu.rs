@@ -3,12 +3,15 @@
 use crate::{
     bindings,
     error::{get_errno, Error},
+    smart_port::{DeviceType, PortMonitor},
+    units::Angle,
 };
 use alloc::format;
 
 /// A struct which represents a V5 smart port configured as a inertial sensor.
 pub struct InertialSensor {
     port: u8,
+    monitor: PortMonitor,
 }
 
 impl InertialSensor {
@@ -20,7 +23,10 @@ impl InertialSensor {
     /// mutable references to the same inertial sensor. You likely want to
     /// implement [`Robot::new()`](crate::robot::Robot::new()) instead.
     pub unsafe fn new(port: u8) -> InertialSensor {
-        InertialSensor { port }
+        InertialSensor {
+            port,
+            monitor: PortMonitor::new(port, DeviceType::Imu),
+        }
     }
 
     /// Calibrate IMU.
@@ -266,6 +272,102 @@ impl InertialSensor {
             _ => Ok(()),
         }
     }
+
+    #[inline]
+    /// Gets the Inertial Sensor’s total rotation about the z-axis; see
+    /// [`InertialSensor::get_rotation()`].
+    pub fn rotation(&self) -> Result<Angle, InertialSensorError> {
+        Ok(Angle::from_degrees(self.get_rotation()?))
+    }
+
+    #[inline]
+    /// Gets the Inertial Sensor’s heading; see
+    /// [`InertialSensor::get_heading()`].
+    pub fn heading(&self) -> Result<Angle, InertialSensorError> {
+        Ok(Angle::from_degrees(self.get_heading()?))
+    }
+
+    #[inline]
+    /// Gets the Inertial Sensor’s pitch; see [`InertialSensor::get_pitch()`].
+    pub fn pitch(&self) -> Result<Angle, InertialSensorError> {
+        Ok(Angle::from_degrees(self.get_pitch()?))
+    }
+
+    #[inline]
+    /// Gets the Inertial Sensor’s roll; see [`InertialSensor::get_roll()`].
+    pub fn roll(&self) -> Result<Angle, InertialSensorError> {
+        Ok(Angle::from_degrees(self.get_roll()?))
+    }
+
+    #[inline]
+    /// Gets the Inertial Sensor’s yaw; see [`InertialSensor::get_yaw()`].
+    pub fn yaw(&self) -> Result<Angle, InertialSensorError> {
+        Ok(Angle::from_degrees(self.get_yaw()?))
+    }
+
+    #[inline]
+    /// Sets the current reading of the Inertial Sensor’s rotation; see
+    /// [`InertialSensor::set_rotation()`].
+    pub fn set_rotation_angle(&mut self, rotation: Angle) -> Result<(), InertialSensorError> {
+        self.set_rotation(rotation.as_degrees())
+    }
+
+    #[inline]
+    /// Sets the current reading of the Inertial Sensor’s heading; see
+    /// [`InertialSensor::set_heading()`].
+    pub fn set_heading_angle(&mut self, heading: Angle) -> Result<(), InertialSensorError> {
+        self.set_heading(heading.as_degrees())
+    }
+
+    #[inline]
+    /// Sets the current reading of the Inertial Sensor’s pitch; see
+    /// [`InertialSensor::set_pitch()`].
+    pub fn set_pitch_angle(&mut self, pitch: Angle) -> Result<(), InertialSensorError> {
+        self.set_pitch(pitch.as_degrees())
+    }
+
+    #[inline]
+    /// Sets the current reading of the Inertial Sensor’s roll; see
+    /// [`InertialSensor::set_roll()`].
+    pub fn set_roll_angle(&mut self, roll: Angle) -> Result<(), InertialSensorError> {
+        self.set_roll(roll.as_degrees())
+    }
+
+    #[inline]
+    /// Sets the current reading of the Inertial Sensor’s yaw; see
+    /// [`InertialSensor::set_yaw()`].
+    pub fn set_yaw_angle(&mut self, yaw: Angle) -> Result<(), InertialSensorError> {
+        self.set_yaw(yaw.as_degrees())
+    }
+
+    /// Checks whether the sensor has been plugged back in since the last
+    /// check. Returns `true` if the sensor was reconnected.
+    ///
+    /// A reconnected inertial sensor must be recalibrated (see
+    /// [`InertialSensor::calibrate()`]) before its readings can be relied on;
+    /// since this cannot be done transparently, this is never called
+    /// automatically.
+    pub fn check_connection(&self) -> bool {
+        let reconnected = self.monitor.poll();
+        if reconnected {
+            self.monitor.mark_configured();
+        }
+        reconnected
+    }
+
+    #[inline]
+    /// Checks whether the sensor was connected as of the last call to
+    /// [`InertialSensor::check_connection()`].
+    pub fn is_connected(&self) -> bool {
+        self.monitor.is_connected()
+    }
+
+    #[inline]
+    /// Gets the number of times the sensor has been unplugged and plugged back
+    /// in since it was constructed.
+    pub fn reconnect_count(&self) -> u32 {
+        self.monitor.reconnects()
+    }
 }
 
 /// Represents possible errors for inertial sensor operations.
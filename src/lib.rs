@@ -29,6 +29,7 @@ pub mod rotation;
 pub mod rtos;
 pub mod serial;
 pub mod smart_port;
+pub mod units;
 
 #[doc(hidden)]
 pub use spin::once;
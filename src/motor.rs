@@ -3,11 +3,21 @@
 use crate::{
     bindings,
     error::{get_errno, Error},
+    smart_port::{DeviceType, PortMonitor},
+    units::{round, Angle, AngularVelocity, Current, Temperature, Voltage},
 };
 
 /// A struct which represents a V5 smart port configured as a motor.
+///
+/// The motor remembers the configuration which has been applied to it (gearset,
+/// encoder units, reversal, brake mode and limits). If the motor is unplugged
+/// and plugged back in, this configuration is re-applied the next time one of
+/// its methods which talks to the motor is called, or when
+/// [`Motor::check_connection()`] is called explicitly.
 pub struct Motor {
     port: u8,
+    config: MotorConfig,
+    monitor: PortMonitor,
 }
 
 impl Motor {
@@ -24,7 +34,19 @@ impl Motor {
         encoder_units: EncoderUnits,
         reverse: bool,
     ) -> Motor {
-        let mut motor = Motor { port };
+        let mut motor = Motor {
+            port,
+            config: MotorConfig {
+                gearset,
+                encoder_units,
+                reverse,
+                brake_mode: None,
+                current_limit: None,
+                voltage_limit: None,
+                gear_ratio: 1.0,
+            },
+            monitor: PortMonitor::new(port, DeviceType::Motor),
+        };
         motor.set_reversed(reverse).unwrap();
         motor.set_gearing(gearset).unwrap();
         motor.set_encoder_units(encoder_units).unwrap();
@@ -37,6 +59,7 @@ impl Motor {
     /// stick for simple opcontrol use. The actual behavior of the motor is
     /// analogous to use of [`Motor::move_voltage()`].
     pub fn move_i8(&mut self, voltage: i8) -> Result<(), MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_move(self.port, voltage as i32) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -52,10 +75,7 @@ impl Motor {
     /// **Note:** This function simply sets the target for the motor, it does
     /// not block program execution until the movement finishes.
     pub fn move_absolute(&mut self, position: f64, velocity: i32) -> Result<(), MotorError> {
-        match unsafe { bindings::motor_move_absolute(self.port, position, velocity) } {
-            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
-            _ => Ok(()),
-        }
+        self.move_absolute_impl(position, velocity as f64)
     }
 
     /// Sets the relative target position for the motor to move to.
@@ -68,10 +88,7 @@ impl Motor {
     /// **Note:** This function simply sets the target for the motor, it does
     /// not block program execution until the movement finishes.
     pub fn move_relative(&mut self, position: f64, velocity: i32) -> Result<(), MotorError> {
-        match unsafe { bindings::motor_move_relative(self.port, position, velocity) } {
-            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
-            _ => Ok(()),
-        }
+        self.move_relative_impl(position, velocity as f64)
     }
 
     /// Sets the velocity for the motor.
@@ -79,18 +96,17 @@ impl Motor {
     /// This velocity corresponds to different actual speeds depending on the
     /// gearset used for the motor. This results in a range of ±100 for
     /// [`Gearset::ThirtySixToOne`] ±200 for [`Gearset::EighteenToOne`] and ±600
-    /// for [`Gearset::SixToOne`]. The velocity is held with PID to ensure
+    /// for [`Gearset::SixToOne`], divided by the external gear ratio (see
+    /// [`Motor::set_gear_ratio()`]). The velocity is held with PID to ensure
     /// consistent speed.
     pub fn move_velocity(&mut self, velocity: i32) -> Result<(), MotorError> {
-        match unsafe { bindings::motor_move_velocity(self.port, velocity) } {
-            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
-            _ => Ok(()),
-        }
+        self.move_velocity_impl(velocity as f64)
     }
 
     /// Sets the output voltage for the motor from -12000 to 12000 in
     /// millivolts.
     pub fn move_voltage(&mut self, voltage: i32) -> Result<(), MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_move_voltage(self.port, voltage) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -101,7 +117,13 @@ impl Motor {
     /// ([`Motor::move_absolute()`] or [`Motor::move_relative()`]). This
     /// will have no effect if the motor is not following a profiled movement.
     pub fn modify_profiled_velocity(&mut self, velocity: i32) -> Result<(), MotorError> {
-        match unsafe { bindings::motor_modify_profiled_velocity(self.port, velocity) } {
+        self.check_connection()?;
+        match unsafe {
+            bindings::motor_modify_profiled_velocity(
+                self.port,
+                self.to_motor_velocity(velocity as f64),
+            )
+        } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
         }
@@ -109,30 +131,30 @@ impl Motor {
 
     /// Gets the target position set for the motor by the user.
     pub fn get_target_position(&self) -> Result<f64, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_target_position(self.port) } {
             x if x == bindings::PROS_ERR_F_ => Err(MotorError::from_errno()),
-            x => Ok(x),
+            x => Ok(x / self.config.gear_ratio),
         }
     }
 
     /// Gets the velocity commanded to the motor by the user.
     pub fn get_target_velocity(&self) -> Result<i32, MotorError> {
-        match unsafe { bindings::motor_get_target_velocity(self.port) } {
-            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
-            x => Ok(x),
-        }
+        Ok(round(self.target_velocity_impl()?))
     }
 
     /// Gets the actual velocity of the motor.
     pub fn get_actual_velocity(&self) -> Result<f64, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_actual_velocity(self.port) } {
             x if x == bindings::PROS_ERR_F_ => Err(MotorError::from_errno()),
-            x => Ok(x),
+            x => Ok(x / self.config.gear_ratio),
         }
     }
 
     /// Gets the current drawn by the motor in milliamperes.
     pub fn get_current_draw(&self) -> Result<i32, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_current_draw(self.port) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             x => Ok(x),
@@ -141,6 +163,7 @@ impl Motor {
 
     /// Gets the direction of movement for the motor.
     pub fn get_direction(&self) -> Result<Direction, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_direction(self.port) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             1 => Ok(Direction::Positive),
@@ -154,22 +177,27 @@ impl Motor {
 
     /// Gets the efficiency of the motor in percent.
     pub fn get_efficiency(&self) -> Result<f64, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_efficiency(self.port) } {
             x if x == bindings::PROS_ERR_F_ => Err(MotorError::from_errno()),
             x => Ok(x),
         }
     }
 
-    /// Gets the absolute position of the motor in encoder ticks.
+    /// Gets the absolute position of the motor in its encoder units (see
+    /// [`Motor::set_encoder_units()`]), measured at the output of any external
+    /// gearing (see [`Motor::set_gear_ratio()`]).
     pub fn get_position(&self) -> Result<f64, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_position(self.port) } {
             x if x == bindings::PROS_ERR_F_ => Err(MotorError::from_errno()),
-            x => Ok(x),
+            x => Ok(x / self.config.gear_ratio),
         }
     }
 
     /// Gets the power drawn by the motor in Watts.
     pub fn get_power(&self) -> Result<f64, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_power(self.port) } {
             x if x == bindings::PROS_ERR_F_ => Err(MotorError::from_errno()),
             x => Ok(x),
@@ -178,6 +206,7 @@ impl Motor {
 
     /// Gets the temperature of the motor in degrees Celsius.
     pub fn get_temperature(&self) -> Result<f64, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_temperature(self.port) } {
             x if x == bindings::PROS_ERR_F_ => Err(MotorError::from_errno()),
             x => Ok(x),
@@ -186,6 +215,7 @@ impl Motor {
 
     /// Gets the torque of the motor in Newton-Meters.
     pub fn get_torque(&self) -> Result<f64, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_torque(self.port) } {
             x if x == bindings::PROS_ERR_F_ => Err(MotorError::from_errno()),
             x => Ok(x),
@@ -194,6 +224,7 @@ impl Motor {
 
     /// Gets the voltage delivered to the motor in millivolts.
     pub fn get_voltage(&self) -> Result<i32, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_voltage(self.port) } {
             x if x == bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             x => Ok(x),
@@ -202,6 +233,7 @@ impl Motor {
 
     /// Checks if the motor is drawing over its current limit.
     pub fn is_over_current(&self) -> Result<bool, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_is_over_current(self.port) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             0 => Ok(false),
@@ -211,6 +243,7 @@ impl Motor {
 
     /// Checks if the motor's temperature is above its limit.
     pub fn is_over_temp(&self) -> Result<bool, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_is_over_temp(self.port) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             0 => Ok(false),
@@ -220,6 +253,7 @@ impl Motor {
 
     /// Gets the brake mode that was set for the motor.
     pub fn get_brake_mode(&self) -> Result<BrakeMode, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_brake_mode(self.port) } {
             bindings::motor_brake_mode_e_E_MOTOR_BRAKE_BRAKE => Ok(BrakeMode::Brake),
             bindings::motor_brake_mode_e_E_MOTOR_BRAKE_COAST => Ok(BrakeMode::Coast),
@@ -237,6 +271,7 @@ impl Motor {
     /// The default value is 2500 milliamperes, however the effective limit may
     /// be lower if more then 8 motors are competing for power.
     pub fn get_current_limit(&self) -> Result<i32, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_current_limit(self.port) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             x => Ok(x),
@@ -245,10 +280,11 @@ impl Motor {
 
     /// Gets the gearset that was set for the motor.
     pub fn get_gearing(&self) -> Result<Gearset, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_gearing(self.port) } {
-            bindings::motor_gearset_e_E_MOTOR_GEARSET_36 => Ok(Gearset::SixToOne),
+            bindings::motor_gearset_e_E_MOTOR_GEARSET_06 => Ok(Gearset::SixToOne),
             bindings::motor_gearset_e_E_MOTOR_GEARSET_18 => Ok(Gearset::EighteenToOne),
-            bindings::motor_gearset_e_E_MOTOR_GEARSET_06 => Ok(Gearset::ThirtySixToOne),
+            bindings::motor_gearset_e_E_MOTOR_GEARSET_36 => Ok(Gearset::ThirtySixToOne),
             bindings::motor_gearset_e_E_MOTOR_GEARSET_INVALID => Err(MotorError::from_errno()),
             x => panic!(
                 "bindings::motor_get_gearing returned unexpected value: {}.",
@@ -262,6 +298,7 @@ impl Motor {
     /// Default value is 0V, which means that there is no software limitation
     /// imposed on the voltage.
     pub fn get_voltage_limit(&self) -> Result<i32, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_voltage_limit(self.port) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             x => Ok(x),
@@ -272,6 +309,7 @@ impl Motor {
     ///
     /// Returns 1 if the motor has been reversed and 0 if the motor was not.
     pub fn is_reversed(&self) -> Result<bool, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_is_reversed(self.port) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             0 => Ok(false),
@@ -281,6 +319,8 @@ impl Motor {
 
     /// Gets the brake mode that was set for the motor.
     pub fn set_brake_mode(&mut self, mode: BrakeMode) -> Result<(), MotorError> {
+        self.check_connection()?;
+        self.config.brake_mode = Some(mode);
         match unsafe { bindings::motor_set_brake_mode(self.port, mode.into()) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -289,6 +329,8 @@ impl Motor {
 
     /// Sets the current limit for the motor in milliamperes.
     pub fn set_current_limit(&mut self, limit: i32) -> Result<(), MotorError> {
+        self.check_connection()?;
+        self.config.current_limit = Some(limit);
         match unsafe { bindings::motor_set_current_limit(self.port, limit) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -297,6 +339,8 @@ impl Motor {
 
     /// Sets one of [`Gearset`] for the motor.
     pub fn set_gearing(&mut self, gearset: Gearset) -> Result<(), MotorError> {
+        self.check_connection()?;
+        self.config.gearset = gearset;
         match unsafe { bindings::motor_set_gearing(self.port, gearset.into()) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -307,6 +351,8 @@ impl Motor {
     ///
     /// This will invert its movements and the values returned for its position.
     pub fn set_reversed(&mut self, reverse: bool) -> Result<(), MotorError> {
+        self.check_connection()?;
+        self.config.reverse = reverse;
         match unsafe { bindings::motor_set_reversed(self.port, reverse) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -315,6 +361,8 @@ impl Motor {
 
     /// Sets the voltage limit for the motor in Volts.
     pub fn set_voltage_limit(&mut self, limit: i32) -> Result<(), MotorError> {
+        self.check_connection()?;
+        self.config.voltage_limit = Some(limit);
         match unsafe { bindings::motor_set_voltage_limit(self.port, limit) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -323,7 +371,10 @@ impl Motor {
 
     /// Sets the "absolute" zero position of the motor.
     pub fn set_zero_position(&mut self, position: f64) -> Result<(), MotorError> {
-        match unsafe { bindings::motor_set_zero_position(self.port, position) } {
+        self.check_connection()?;
+        match unsafe {
+            bindings::motor_set_zero_position(self.port, position * self.config.gear_ratio)
+        } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
         }
@@ -331,6 +382,7 @@ impl Motor {
 
     /// Sets the "absolute" zero position of the motor to its current position.
     pub fn tare_position(&mut self) -> Result<(), MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_tare_position(self.port) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -339,6 +391,8 @@ impl Motor {
 
     /// Sets the [`EncoderUnits`] for the motor.
     pub fn set_encoder_units(&mut self, units: EncoderUnits) -> Result<(), MotorError> {
+        self.check_connection()?;
+        self.config.encoder_units = units;
         match unsafe { bindings::motor_set_encoder_units(self.port, units.into()) } {
             bindings::PROS_ERR_ => Err(MotorError::from_errno()),
             _ => Ok(()),
@@ -347,6 +401,7 @@ impl Motor {
 
     /// Gets the [`EncoderUnits`] set for the motor.
     pub fn get_encoder_units(&self) -> Result<EncoderUnits, MotorError> {
+        self.check_connection()?;
         match unsafe { bindings::motor_get_encoder_units(self.port) } {
             bindings::motor_encoder_units_e_E_MOTOR_ENCODER_COUNTS => {
                 Ok(EncoderUnits::EncoderTicks)
@@ -361,6 +416,279 @@ impl Motor {
             x => panic!("bindings:get_encoder_units returned unexpected value {}", x),
         }
     }
+
+    /// Sets the target absolute position for the motor to move to, at up to the
+    /// given velocity; see [`Motor::move_absolute()`].
+    pub fn move_to(
+        &mut self,
+        position: Angle,
+        velocity: AngularVelocity,
+    ) -> Result<(), MotorError> {
+        let position = self.angle_to_units(position);
+        self.move_absolute_impl(position, velocity.as_rpm())
+    }
+
+    /// Sets the target position for the motor to move to relative to its
+    /// current target, at up to the given velocity; see
+    /// [`Motor::move_relative()`].
+    pub fn move_by(&mut self, offset: Angle, velocity: AngularVelocity) -> Result<(), MotorError> {
+        let offset = self.angle_to_units(offset);
+        self.move_relative_impl(offset, velocity.as_rpm())
+    }
+
+    #[inline]
+    /// Sets the velocity for the motor; see [`Motor::move_velocity()`].
+    pub fn move_at(&mut self, velocity: AngularVelocity) -> Result<(), MotorError> {
+        self.move_velocity_impl(velocity.as_rpm())
+    }
+
+    #[inline]
+    /// Sets the output voltage for the motor; see [`Motor::move_voltage()`].
+    pub fn apply_voltage(&mut self, voltage: Voltage) -> Result<(), MotorError> {
+        self.move_voltage(round(voltage.as_millivolts()))
+    }
+
+    #[inline]
+    /// Sets the current limit for the motor; see
+    /// [`Motor::set_current_limit()`].
+    pub fn limit_current(&mut self, limit: Current) -> Result<(), MotorError> {
+        self.set_current_limit(round(limit.as_milliamps()))
+    }
+
+    #[inline]
+    /// Sets the voltage limit for the motor; see
+    /// [`Motor::set_voltage_limit()`].
+    pub fn limit_voltage(&mut self, limit: Voltage) -> Result<(), MotorError> {
+        self.set_voltage_limit(round(limit.as_volts()))
+    }
+
+    #[inline]
+    /// Gets the absolute position of the motor.
+    pub fn position(&self) -> Result<Angle, MotorError> {
+        Ok(self.units_to_angle(self.get_position()?))
+    }
+
+    #[inline]
+    /// Gets the target position set for the motor by the user.
+    pub fn target_position(&self) -> Result<Angle, MotorError> {
+        Ok(self.units_to_angle(self.get_target_position()?))
+    }
+
+    #[inline]
+    /// Gets the actual velocity of the motor.
+    pub fn actual_velocity(&self) -> Result<AngularVelocity, MotorError> {
+        Ok(AngularVelocity::from_rpm(self.get_actual_velocity()?))
+    }
+
+    #[inline]
+    /// Gets the velocity commanded to the motor by the user.
+    pub fn target_velocity(&self) -> Result<AngularVelocity, MotorError> {
+        Ok(AngularVelocity::from_rpm(self.target_velocity_impl()?))
+    }
+
+    #[inline]
+    /// Gets the current drawn by the motor.
+    pub fn current_draw(&self) -> Result<Current, MotorError> {
+        Ok(Current::from_milliamps(self.get_current_draw()? as f64))
+    }
+
+    #[inline]
+    /// Gets the current limit for the motor.
+    pub fn current_limit(&self) -> Result<Current, MotorError> {
+        Ok(Current::from_milliamps(self.get_current_limit()? as f64))
+    }
+
+    #[inline]
+    /// Gets the voltage delivered to the motor.
+    pub fn voltage(&self) -> Result<Voltage, MotorError> {
+        Ok(Voltage::from_millivolts(self.get_voltage()? as f64))
+    }
+
+    #[inline]
+    /// Gets the voltage limit set by the user.
+    pub fn voltage_limit(&self) -> Result<Voltage, MotorError> {
+        Ok(Voltage::from_volts(self.get_voltage_limit()? as f64))
+    }
+
+    #[inline]
+    /// Gets the temperature of the motor.
+    pub fn temperature(&self) -> Result<Temperature, MotorError> {
+        Ok(Temperature::from_celsius(self.get_temperature()?))
+    }
+
+    /// Sets the external gear ratio between the motor and the output shaft of
+    /// the mechanism it drives, as the number of motor rotations per output
+    /// rotation. For example, a 12-tooth gear on the motor driving a 60-tooth
+    /// gear gives a ratio of 5.0.
+    ///
+    /// Positions and velocities passed to and returned from the motor are then
+    /// measured at the output shaft. The default ratio is 1.0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ratio is not positive.
+    pub fn set_gear_ratio(&mut self, ratio: f64) {
+        assert!(ratio > 0.0, "gear ratio must be positive, got {}", ratio);
+        self.config.gear_ratio = ratio;
+    }
+
+    #[inline]
+    /// Gets the external gear ratio; see [`Motor::set_gear_ratio()`].
+    pub fn gear_ratio(&self) -> f64 {
+        self.config.gear_ratio
+    }
+
+    /// Checks whether the motor has lost its stored configuration, e.g. by
+    /// being unplugged and plugged back in, re-applying it if so. Returns
+    /// `true` if the configuration was re-applied.
+    ///
+    /// Besides watching for the motor being unplugged, this reads the
+    /// configuration back from the motor, so that a reconnection which
+    /// happened between calls is also noticed. If re-applying the
+    /// configuration fails, it is retried on the next call.
+    ///
+    /// This is called automatically by every method which talks to the motor,
+    /// so that readings are never taken with a stale configuration.
+    pub fn check_connection(&self) -> Result<bool, MotorError> {
+        let stale = self.monitor.poll() || (self.monitor.is_connected() && !self.config_matches());
+        if !stale {
+            return Ok(false);
+        }
+        self.reconfigure()?;
+        self.monitor.mark_configured();
+        Ok(true)
+    }
+
+    #[inline]
+    /// Checks whether the motor was connected as of the last call to
+    /// [`Motor::check_connection()`].
+    pub fn is_connected(&self) -> bool {
+        self.monitor.is_connected()
+    }
+
+    #[inline]
+    /// Gets the number of times the motor has been unplugged and plugged back
+    /// in since it was constructed.
+    pub fn reconnect_count(&self) -> u32 {
+        self.monitor.reconnects()
+    }
+
+    fn move_absolute_impl(&mut self, position: f64, velocity: f64) -> Result<(), MotorError> {
+        self.check_connection()?;
+        match unsafe {
+            bindings::motor_move_absolute(
+                self.port,
+                position * self.config.gear_ratio,
+                self.to_motor_velocity(velocity),
+            )
+        } {
+            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
+            _ => Ok(()),
+        }
+    }
+
+    fn move_relative_impl(&mut self, position: f64, velocity: f64) -> Result<(), MotorError> {
+        self.check_connection()?;
+        match unsafe {
+            bindings::motor_move_relative(
+                self.port,
+                position * self.config.gear_ratio,
+                self.to_motor_velocity(velocity),
+            )
+        } {
+            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
+            _ => Ok(()),
+        }
+    }
+
+    fn move_velocity_impl(&mut self, velocity: f64) -> Result<(), MotorError> {
+        self.check_connection()?;
+        match unsafe { bindings::motor_move_velocity(self.port, self.to_motor_velocity(velocity)) }
+        {
+            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
+            _ => Ok(()),
+        }
+    }
+
+    fn target_velocity_impl(&self) -> Result<f64, MotorError> {
+        self.check_connection()?;
+        match unsafe { bindings::motor_get_target_velocity(self.port) } {
+            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
+            x => Ok(x as f64 / self.config.gear_ratio),
+        }
+    }
+
+    #[inline]
+    fn to_motor_velocity(&self, velocity: f64) -> i32 {
+        round(velocity * self.config.gear_ratio)
+    }
+
+    fn units_to_angle(&self, position: f64) -> Angle {
+        Angle::from_rotations(position / self.config.encoder_units.per_rev(self.config.gearset))
+    }
+
+    fn angle_to_units(&self, angle: Angle) -> f64 {
+        angle.as_rotations() * self.config.encoder_units.per_rev(self.config.gearset)
+    }
+
+    fn config_matches(&self) -> bool {
+        let config = &self.config;
+        unsafe {
+            bindings::motor_get_gearing(self.port) == config.gearset.into()
+                && bindings::motor_get_encoder_units(self.port) == config.encoder_units.into()
+                && (bindings::motor_is_reversed(self.port) != 0) == config.reverse
+                && config.brake_mode.map_or(true, |mode| {
+                    bindings::motor_get_brake_mode(self.port) == mode.into()
+                })
+                && config.current_limit.map_or(true, |limit| {
+                    bindings::motor_get_current_limit(self.port) == limit
+                })
+                && config.voltage_limit.map_or(true, |limit| {
+                    bindings::motor_get_voltage_limit(self.port) == limit
+                })
+        }
+    }
+
+    fn reconfigure(&self) -> Result<(), MotorError> {
+        let check = |r| match r {
+            bindings::PROS_ERR_ => Err(MotorError::from_errno()),
+            _ => Ok(()),
+        };
+        let config = &self.config;
+        unsafe {
+            check(bindings::motor_set_gearing(
+                self.port,
+                config.gearset.into(),
+            ))?;
+            check(bindings::motor_set_encoder_units(
+                self.port,
+                config.encoder_units.into(),
+            ))?;
+            check(bindings::motor_set_reversed(self.port, config.reverse))?;
+            if let Some(mode) = config.brake_mode {
+                check(bindings::motor_set_brake_mode(self.port, mode.into()))?;
+            }
+            if let Some(limit) = config.current_limit {
+                check(bindings::motor_set_current_limit(self.port, limit))?;
+            }
+            if let Some(limit) = config.voltage_limit {
+                check(bindings::motor_set_voltage_limit(self.port, limit))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The configuration of a [`Motor`] as set by the user, which is re-applied
+/// when the motor is reconnected.
+struct MotorConfig {
+    gearset: Gearset,
+    encoder_units: EncoderUnits,
+    reverse: bool,
+    brake_mode: Option<BrakeMode>,
+    current_limit: Option<i32>,
+    voltage_limit: Option<i32>,
+    gear_ratio: f64,
 }
 
 /// Represents possible errors for motor operations.
@@ -426,6 +754,65 @@ pub enum Gearset {
     ThirtySixToOne,
 }
 
+impl Gearset {
+    #[inline]
+    /// Gets the number of encoder ticks per revolution of the motor's output
+    /// shaft with this gearset.
+    pub fn ticks_per_rev(self) -> f64 {
+        match self {
+            Gearset::SixToOne => 300.0,
+            Gearset::EighteenToOne => 900.0,
+            Gearset::ThirtySixToOne => 1800.0,
+        }
+    }
+
+    #[inline]
+    /// Gets the maximum velocity of the motor's output shaft with this gearset,
+    /// in rotations per minute.
+    pub fn max_rpm(self) -> f64 {
+        match self {
+            Gearset::SixToOne => 600.0,
+            Gearset::EighteenToOne => 200.0,
+            Gearset::ThirtySixToOne => 100.0,
+        }
+    }
+
+    #[inline]
+    /// Converts a position between encoder units for a motor with this
+    /// gearset.
+    pub fn convert(self, position: f64, from: EncoderUnits, to: EncoderUnits) -> f64 {
+        position / from.per_rev(self) * to.per_rev(self)
+    }
+
+    #[inline]
+    /// Converts a number of encoder ticks to degrees.
+    pub fn ticks_to_degrees(self, ticks: f64) -> f64 {
+        self.convert(ticks, EncoderUnits::EncoderTicks, EncoderUnits::Degrees)
+    }
+
+    #[inline]
+    /// Converts a number of encoder ticks to rotations.
+    pub fn ticks_to_rotations(self, ticks: f64) -> f64 {
+        self.convert(ticks, EncoderUnits::EncoderTicks, EncoderUnits::Rotations)
+    }
+
+    #[inline]
+    /// Converts a number of degrees to encoder ticks.
+    pub fn degrees_to_ticks(self, degrees: f64) -> f64 {
+        self.convert(degrees, EncoderUnits::Degrees, EncoderUnits::EncoderTicks)
+    }
+
+    #[inline]
+    /// Converts a number of rotations to encoder ticks.
+    pub fn rotations_to_ticks(self, rotations: f64) -> f64 {
+        self.convert(
+            rotations,
+            EncoderUnits::Rotations,
+            EncoderUnits::EncoderTicks,
+        )
+    }
+}
+
 impl From<Gearset> for bindings::motor_gearset_e {
     fn from(gearset: Gearset) -> Self {
         match gearset {
@@ -459,6 +846,19 @@ pub enum EncoderUnits {
     Rotations,
 }
 
+impl EncoderUnits {
+    #[inline]
+    /// Gets the number of these units per revolution of the output shaft of a
+    /// motor with the given gearset.
+    pub fn per_rev(self, gearset: Gearset) -> f64 {
+        match self {
+            EncoderUnits::EncoderTicks => gearset.ticks_per_rev(),
+            EncoderUnits::Degrees => 360.0,
+            EncoderUnits::Rotations => 1.0,
+        }
+    }
+}
+
 impl From<EncoderUnits> for bindings::motor_encoder_units_e {
     fn from(units: EncoderUnits) -> Self {
         match units {
@@ -17,3 +17,4 @@ pub use crate::robot::*;
 pub use crate::rotation::*;
 pub use crate::rtos::*;
 pub use crate::smart_port::*;
+pub use crate::units::*;
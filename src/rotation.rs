@@ -3,11 +3,19 @@
 use crate::{
     bindings,
     error::{get_errno, Error},
+    smart_port::{DeviceType, PortMonitor},
+    units::{round, Angle, AngularVelocity},
 };
 
 /// A struct which represents a V5 smart port configured as a rotation sensor.
+///
+/// The sensor remembers whether it has been reversed, and re-applies this the
+/// next time one of its methods is called after it is unplugged and plugged
+/// back in; see [`RotationSensor::check_connection()`].
 pub struct RotationSensor {
     port: u8,
+    reversed: bool,
+    monitor: PortMonitor,
 }
 
 impl RotationSensor {
@@ -19,7 +27,11 @@ impl RotationSensor {
     /// mutable references to the same rotation sensor. You likely want to
     /// implement [`Robot::new()`](crate::robot::Robot::new()) instead.
     pub unsafe fn new(port: u8, reversed: bool) -> Result<Self, RotationSensorError> {
-        let mut sensor = Self { port };
+        let mut sensor = Self {
+            port,
+            reversed,
+            monitor: PortMonitor::new(port, DeviceType::Rotation),
+        };
 
         sensor.set_reversed(reversed)?;
 
@@ -29,6 +41,7 @@ impl RotationSensor {
     /// Reset the current absolute position to be the same as the Rotation
     /// Sensor angle.
     pub fn reset(&mut self) -> Result<(), RotationSensorError> {
+        self.check_connection()?;
         match unsafe { bindings::rotation_reset(self.port) } {
             bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             _ => Ok(()),
@@ -37,6 +50,7 @@ impl RotationSensor {
 
     /// Set the Rotation sensor to a desired rotation value in centidegrees.
     pub fn set_position(&mut self, rotation: u32) -> Result<(), RotationSensorError> {
+        self.check_connection()?;
         match unsafe { bindings::rotation_set_position(self.port, rotation) } {
             bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             _ => Ok(()),
@@ -46,6 +60,7 @@ impl RotationSensor {
     /// Reset the current absolute position to be the same as the Rotation
     /// Sensor angle.
     pub fn reset_position(&mut self) -> Result<(), RotationSensorError> {
+        self.check_connection()?;
         match unsafe { bindings::rotation_reset_position(self.port) } {
             bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             _ => Ok(()),
@@ -54,6 +69,7 @@ impl RotationSensor {
 
     /// Get the Rotation Sensor’s current position in centidegrees
     pub fn get_position(&self) -> Result<i32, RotationSensorError> {
+        self.check_connection()?;
         match unsafe { bindings::rotation_get_position(self.port) } {
             x if x == bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             x => Ok(x),
@@ -62,6 +78,7 @@ impl RotationSensor {
 
     /// Get the Rotation Sensor’s current velocity in centidegrees per second
     pub fn get_velocity(&self) -> Result<i32, RotationSensorError> {
+        self.check_connection()?;
         match unsafe { bindings::rotation_get_velocity(self.port) } {
             x if x == bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             x => Ok(x),
@@ -70,6 +87,7 @@ impl RotationSensor {
 
     /// Get the Rotation Sensor’s current angle in centidegrees (0-36000)
     pub fn get_angle(&self) -> Result<i32, RotationSensorError> {
+        self.check_connection()?;
         match unsafe { bindings::rotation_get_angle(self.port) } {
             x if x == bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             x => Ok(x),
@@ -78,6 +96,8 @@ impl RotationSensor {
 
     /// Set the rotation direction of the sensor
     pub fn set_reversed(&mut self, reverse: bool) -> Result<(), RotationSensorError> {
+        self.check_connection()?;
+        self.reversed = reverse;
         match unsafe { bindings::rotation_set_reversed(self.port, reverse) } {
             bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             _ => Ok(()),
@@ -86,6 +106,8 @@ impl RotationSensor {
 
     /// Reverses the rotational sensor’s direction
     pub fn reverse(&mut self) -> Result<(), RotationSensorError> {
+        self.check_connection()?;
+        self.reversed = !self.reversed;
         match unsafe { bindings::rotation_reverse(self.port) } {
             bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             _ => Ok(()),
@@ -94,11 +116,84 @@ impl RotationSensor {
 
     /// Get the Rotation Sensor’s reversed flag
     pub fn get_reversed(&self) -> Result<bool, RotationSensorError> {
+        self.check_connection()?;
         match unsafe { bindings::rotation_get_reversed(self.port) } {
             x if x == bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
             x => Ok(x != 0),
         }
     }
+
+    /// Sets the Rotation sensor to a desired rotation value; see
+    /// [`RotationSensor::set_position()`].
+    pub fn set_position_angle(&mut self, rotation: Angle) -> Result<(), RotationSensorError> {
+        // PROS takes the position as unsigned but stores it as signed, so
+        // negative positions are passed via their two's complement bits.
+        self.set_position(round(rotation.as_centidegrees()) as u32)
+    }
+
+    #[inline]
+    /// Gets the Rotation Sensor’s current position.
+    pub fn position(&self) -> Result<Angle, RotationSensorError> {
+        Ok(Angle::from_centidegrees(self.get_position()? as f64))
+    }
+
+    #[inline]
+    /// Gets the Rotation Sensor’s current velocity.
+    pub fn velocity(&self) -> Result<AngularVelocity, RotationSensorError> {
+        Ok(AngularVelocity::from_centidegrees_per_second(
+            self.get_velocity()? as f64,
+        ))
+    }
+
+    #[inline]
+    /// Gets the Rotation Sensor’s current angle, from 0 to 360 degrees.
+    pub fn angle(&self) -> Result<Angle, RotationSensorError> {
+        Ok(Angle::from_centidegrees(self.get_angle()? as f64))
+    }
+
+    /// Checks whether the sensor has lost its reversed flag, e.g. by being
+    /// unplugged and plugged back in, re-applying it if so. Returns `true` if
+    /// the flag was re-applied.
+    ///
+    /// Besides watching for the sensor being unplugged, this reads the flag
+    /// back from the sensor, so that a reconnection which happened between
+    /// calls is also noticed. If re-applying the flag fails, it is retried on
+    /// the next call.
+    ///
+    /// This is called automatically by every method which talks to the sensor,
+    /// so that readings are never taken with the wrong direction.
+    pub fn check_connection(&self) -> Result<bool, RotationSensorError> {
+        let stale =
+            self.monitor.poll() || (self.monitor.is_connected() && !self.reversed_matches());
+        if !stale {
+            return Ok(false);
+        }
+        match unsafe { bindings::rotation_set_reversed(self.port, self.reversed) } {
+            bindings::PROS_ERR_ => Err(RotationSensorError::from_errno()),
+            _ => {
+                self.monitor.mark_configured();
+                Ok(true)
+            }
+        }
+    }
+
+    #[inline]
+    /// Checks whether the sensor was connected as of the last call to
+    /// [`RotationSensor::check_connection()`].
+    pub fn is_connected(&self) -> bool {
+        self.monitor.is_connected()
+    }
+
+    #[inline]
+    /// Gets the number of times the sensor has been unplugged and plugged back
+    /// in since it was constructed.
+    pub fn reconnect_count(&self) -> u32 {
+        self.monitor.reconnects()
+    }
+
+    fn reversed_matches(&self) -> bool {
+        (unsafe { bindings::rotation_get_reversed(self.port) } != 0) == self.reversed
+    }
 }
 
 /// Represents possible errors for distance sensor operations.
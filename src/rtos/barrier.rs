@@ -0,0 +1,125 @@
+use owner_monad::{Owner, OwnerMut};
+
+use super::{handle_event, select, Event, EventHandle, GenericSleep, Instant, Mutex, Selectable};
+use crate::error::Error;
+
+/// A barrier which makes a fixed number of tasks wait until all of them have
+/// reached it.
+///
+/// The barrier is reusable: once all tasks have arrived and been released, the
+/// next group of arrivals waits again.
+pub struct Barrier(Mutex<BarrierData>);
+
+impl Barrier {
+    #[inline]
+    /// Creates a new barrier for `n` tasks. Panics on failure; see
+    /// [`Barrier::try_new()`].
+    pub fn new(n: usize) -> Self {
+        Self::try_new(n).unwrap_or_else(|err| panic!("failed to create barrier: {:?}", err))
+    }
+
+    /// Creates a new barrier for `n` tasks.
+    pub fn try_new(n: usize) -> Result<Self, Error> {
+        Ok(Self(Mutex::try_new(BarrierData {
+            n,
+            count: 0,
+            generation: 0,
+            event: Event::new(),
+        })?))
+    }
+
+    #[inline]
+    /// Waits until all tasks have reached the barrier. The result is `true`
+    /// for exactly one task in each group (the last one to arrive).
+    pub fn wait(&self) -> bool {
+        select(self.select_wait())
+    }
+
+    /// A [`Selectable`] event which occurs when all tasks have reached the
+    /// barrier. The result is `true` for exactly one task in each group (the
+    /// last one to arrive).
+    ///
+    /// The current task arrives at the barrier when the event is created; if
+    /// the event is dropped before it occurs, the arrival is withdrawn.
+    pub fn select_wait(&'_ self) -> impl Selectable<bool> + '_ {
+        struct BarrierSelect<'a> {
+            barrier: &'a Barrier,
+            generation: u32,
+            leader: bool,
+            done: bool,
+            handle: EventHandle<&'a Mutex<BarrierData>>,
+        }
+
+        impl<'a> Selectable<bool> for BarrierSelect<'a> {
+            fn poll(mut self) -> Result<bool, Self> {
+                let generation = self.generation;
+                if self.leader
+                    || self
+                        .handle
+                        .with(|mtx| mtx.lock().generation != generation)
+                        .unwrap_or(false)
+                {
+                    self.done = true;
+                    Ok(self.leader)
+                } else {
+                    Err(self)
+                }
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if self.leader || self.barrier.0.lock().generation != self.generation {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                } else {
+                    GenericSleep::NotifyTake(None)
+                }
+            }
+        }
+
+        impl<'a> Drop for BarrierSelect<'a> {
+            fn drop(&mut self) {
+                if !self.done {
+                    let mut lock = self.barrier.0.lock();
+                    if lock.generation == self.generation {
+                        lock.count -= 1;
+                    }
+                }
+            }
+        }
+
+        let (generation, leader) = {
+            let mut lock = self.0.lock();
+            let generation = lock.generation;
+            lock.count += 1;
+            let leader = lock.count >= lock.n;
+            if leader {
+                lock.count = 0;
+                lock.generation = lock.generation.wrapping_add(1);
+                lock.event.notify();
+            }
+            (generation, leader)
+        };
+        BarrierSelect {
+            barrier: self,
+            generation,
+            leader,
+            done: false,
+            handle: handle_event(&self.0),
+        }
+    }
+}
+
+struct BarrierData {
+    n: usize,
+    count: usize,
+    generation: u32,
+    event: Event,
+}
+
+impl OwnerMut<Event> for &Mutex<BarrierData> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
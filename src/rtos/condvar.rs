@@ -0,0 +1,91 @@
+use core::time::Duration;
+
+use super::{delay, select, MutexGuard, Notify};
+use crate::{error::Error, select};
+
+/// A condition variable, which allows tasks to wait for a condition on data
+/// protected by a [`Mutex`] to become true.
+///
+/// Waiting atomically releases the mutex and reacquires it once the task is
+/// woken. Wakeups may be spurious, so the condition should always be checked
+/// again in a loop (or see [`Condvar::wait_while()`]).
+///
+/// Since [`Mutex`] is recursive, waiting only releases it if the guard is the
+/// task's only hold on it.
+pub struct Condvar(Notify);
+
+impl Condvar {
+    #[inline]
+    /// Creates a new condition variable. Panics on failure; see
+    /// [`Condvar::try_new()`].
+    pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|err| panic!("failed to create condvar: {:?}", err))
+    }
+
+    #[inline]
+    /// Creates a new condition variable.
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self(Notify::try_new()?))
+    }
+
+    /// Releases the mutex and waits to be notified, then reacquires the mutex.
+    pub fn wait<'a, T: ?Sized>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        let mutex = MutexGuard::mutex(&guard);
+        // The wait must be registered before the mutex is released, so that a
+        // notification sent in between is not missed.
+        let notified = self.0.notified();
+        drop(guard);
+        select(notified);
+        mutex.lock()
+    }
+
+    /// Releases the mutex and waits to be notified, or for `timeout` to
+    /// elapse, then reacquires the mutex. The result is the new guard along
+    /// with `true` if the timeout elapsed without a notification.
+    pub fn wait_timeout<'a, T: ?Sized>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        timeout: Duration,
+    ) -> (MutexGuard<'a, T>, bool) {
+        let mutex = MutexGuard::mutex(&guard);
+        let notified = self.0.notified();
+        let timeout = delay(timeout);
+        drop(guard);
+        let timed_out = select! {
+            _ = notified => false,
+            _ = timeout => true,
+        };
+        (mutex.lock(), timed_out)
+    }
+
+    /// Waits, as per [`Condvar::wait()`], for as long as `condition` returns
+    /// `true`.
+    pub fn wait_while<'a, T: ?Sized>(
+        &self,
+        mut guard: MutexGuard<'a, T>,
+        mut condition: impl FnMut(&mut T) -> bool,
+    ) -> MutexGuard<'a, T> {
+        while condition(&mut guard) {
+            guard = self.wait(guard);
+        }
+        guard
+    }
+
+    #[inline]
+    /// Wakes one task which is waiting on the condition variable, if any.
+    pub fn notify_one(&self) {
+        self.0.notify_one_impl(false);
+    }
+
+    #[inline]
+    /// Wakes all tasks which are waiting on the condition variable.
+    pub fn notify_all(&self) {
+        self.0.notify_all();
+    }
+}
+
+impl Default for Condvar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,18 +1,31 @@
 use alloc::{
+    format,
     sync::{Arc, Weak},
     vec::Vec,
 };
 use by_address::ByAddress;
-use core::{cmp::min, time::Duration};
+use core::{
+    any::{Any, TypeId},
+    cmp::min,
+    fmt::{self, Display, Formatter},
+    mem::replace,
+    time::Duration,
+};
 use owner_monad::OwnerMut;
 use raii_map::set::{insert, Set, SetHandle};
 
 use super::{
     handle_event, time_since_start, Event, EventHandle, GenericSleep, Instant, Mutex, Selectable,
 };
-use crate::select_merge;
+use crate::{error::Error, select_merge};
+
+type AnyValue = Arc<dyn Any + Send + Sync>;
 
-type ContextValue = (Option<Instant>, Mutex<Option<ContextData>>);
+struct ContextValue {
+    deadline: Option<Instant>,
+    state: Mutex<ContextState>,
+    values: Vec<(TypeId, AnyValue)>,
+}
 
 #[derive(Clone)]
 /// Represents an ongoing operation which could be cancelled in the future.
@@ -32,6 +45,18 @@ type ContextValue = (Option<Instant>, Mutex<Option<ContextData>>);
 ///
 /// A context can be "forked", which creates a new child context. This new
 /// context can optionally be created with a deadline.
+///
+/// # Values
+///
+/// A context can carry values, at most one of each type, which are inherited
+/// by its children; see [`Context::with_value()`] and [`Context::value()`].
+/// This allows data such as match metadata to be passed down to everything
+/// running under a context without threading it through every call.
+///
+/// # Cancellation Reasons
+///
+/// Once a context is cancelled, the reason why is available from
+/// [`Context::cancel_reason()`]; see [`CancelReason`].
 pub struct Context(Arc<ContextValue>);
 
 impl Context {
@@ -39,13 +64,67 @@ impl Context {
     /// Creates a new global context (i.e., one which has no parent or
     /// deadline).
     pub fn new_global() -> Self {
-        Self::new_internal(&[], None)
+        Self::new_internal(&[], None, None)
     }
 
     #[inline]
     /// Cancels a context. This is a no-op if the context is already cancelled.
     pub fn cancel(&self) {
-        cancel(&self.0.as_ref().1);
+        cancel(&self.0.state, CancelReason::Cancelled);
+    }
+
+    /// Gets the reason the context was cancelled, or `None` if it has not
+    /// been cancelled.
+    pub fn cancel_reason(&self) -> Option<CancelReason> {
+        let lock = self.0.state.lock();
+        match &*lock {
+            ContextState::Cancelled(reason) => Some(*reason),
+            _ if self.is_expired() => {
+                drop(lock);
+                cancel(&self.0.state, CancelReason::DeadlineExceeded);
+                self.cancel_reason()
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    /// Returns `true` if the context has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel_reason().is_some()
+    }
+
+    #[inline]
+    /// Returns `Err` with the reason the context was cancelled, if it has
+    /// been; this allows cancellation to be propagated with `?`.
+    pub fn check(&self) -> Result<(), CancelReason> {
+        self.cancel_reason().map_or(Ok(()), Err)
+    }
+
+    #[inline]
+    /// Gets the deadline of the context, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.0.deadline
+    }
+
+    /// Forks the context, attaching the given value to the new context.
+    ///
+    /// The value is visible via [`Context::value()`] from the new context and
+    /// from any contexts forked from it, unless they attach a value of the
+    /// same type themselves.
+    pub fn with_value<T: Any + Send + Sync>(&self, value: T) -> Context {
+        Self::new_internal(&[self], None, Some((TypeId::of::<T>(), Arc::new(value))))
+    }
+
+    /// Gets the value of type `T` attached to the context or inherited from
+    /// its parents, if any. If a context has several parents with a value of
+    /// type `T`, the first parent's value is used.
+    pub fn value<T: Any>(&self) -> Option<&T> {
+        self.0
+            .values
+            .iter()
+            .find(|(id, _)| *id == TypeId::of::<T>())
+            .and_then(|(_, value)| value.downcast_ref())
     }
 
     /// A [`Selectable`] event which occurs when the context is
@@ -56,21 +135,14 @@ impl Context {
 
         impl<'a> Selectable for ContextSelect<'a> {
             fn poll(self) -> Result<(), Self> {
-                let mut lock = self.0 .0 .1.lock();
-                let opt = &mut lock.as_mut();
-                if opt.is_some() {
-                    if self.0 .0 .0.map_or(false, |v| v <= time_since_start()) {
-                        opt.take();
-                        Ok(())
-                    } else {
-                        Err(self)
-                    }
-                } else {
+                if self.0.is_cancelled() {
                     Ok(())
+                } else {
+                    Err(self)
                 }
             }
             fn sleep(&self) -> GenericSleep {
-                GenericSleep::NotifyTake(self.0 .0 .0)
+                GenericSleep::NotifyTake(self.0 .0.deadline)
             }
         }
 
@@ -90,13 +162,46 @@ impl Context {
         }
     }
 
-    fn new_internal(parents: &[&Self], mut deadline: Option<Instant>) -> Self {
+    /// Creates a [`Selectable`] event which occurs when either the given
+    /// `event` resolves, or when the context is cancelled, whichever occurs
+    /// first. Equivalent to [`Context::wrap()`], except that cancellation is
+    /// reported with its reason.
+    pub fn wrap_result<'a, T: 'a>(
+        &'a self,
+        event: impl Selectable<T> + 'a,
+    ) -> impl Selectable<Result<T, CancelReason>> + 'a {
+        select_merge! {
+            r = event => Ok(r),
+            _ = self.done() => Err(self.cancel_reason().unwrap_or(CancelReason::Cancelled)),
+        }
+    }
+
+    #[inline]
+    fn is_expired(&self) -> bool {
+        self.0.deadline.map_or(false, |v| v <= time_since_start())
+    }
+
+    fn new_internal(
+        parents: &[&Self],
+        mut deadline: Option<Instant>,
+        value: Option<(TypeId, AnyValue)>,
+    ) -> Self {
         deadline = parents
             .iter()
-            .filter_map(|parent| parent.0 .0)
+            .filter_map(|parent| parent.0.deadline)
             .min()
             .map_or(deadline, |d1| Some(deadline.map_or(d1, |d2| min(d1, d2))));
-        let ctx = Self(Arc::new((deadline, Mutex::new(None))));
+        let mut values: Vec<(TypeId, AnyValue)> = value.into_iter().collect();
+        for (id, value) in parents.iter().flat_map(|parent| parent.0.values.iter()) {
+            if values.iter().all(|(v, _)| v != id) {
+                values.push((*id, value.clone()));
+            }
+        }
+        let ctx = Self(Arc::new(ContextValue {
+            deadline,
+            state: Mutex::new(ContextState::Starting),
+            values,
+        }));
         let mut parent_handles = Vec::new();
         parent_handles.reserve_exact(parents.len());
         for parent in parents {
@@ -106,14 +211,21 @@ impl Context {
             ) {
                 parent_handles.push(handle);
             } else {
+                cancel(&ctx.0.state, CancelReason::ParentCancelled);
                 return ctx;
             }
         }
-        *ctx.0 .1.lock() = Some(ContextData {
-            _parents: parent_handles,
-            event: Event::new(),
-            children: Set::new(),
-        });
+        let mut lock = ctx.0.state.lock();
+        // A parent may have been cancelled after the context was added to it,
+        // in which case the context has already been cancelled too.
+        if let ContextState::Starting = *lock {
+            *lock = ContextState::Active(ContextData {
+                _parents: parent_handles,
+                event: Event::new(),
+                children: Set::new(),
+            });
+        }
+        drop(lock);
         ctx
     }
 }
@@ -152,15 +264,53 @@ impl ParentContext for Context {
 impl ParentContext for [&Context] {
     #[inline]
     fn fork(&self) -> Context {
-        Context::new_internal(self, None)
+        Context::new_internal(self, None, None)
     }
 
     #[inline]
     fn fork_with_deadline(&self, deadline: Instant) -> Context {
-        Context::new_internal(self, Some(deadline))
+        Context::new_internal(self, Some(deadline), None)
     }
 }
 
+/// The reason a [`Context`] was cancelled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CancelReason {
+    /// The context was cancelled explicitly via [`Context::cancel()`], or
+    /// because the last copy of it was dropped.
+    Cancelled,
+    /// The context's deadline passed.
+    DeadlineExceeded,
+    /// A parent of the context was cancelled.
+    ParentCancelled,
+    /// The context was replaced by a new one in a [`ContextWrapper`] (e.g.,
+    /// because the robot or a state machine changed state).
+    ModeChange,
+}
+
+impl Display for CancelReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CancelReason::Cancelled => "context cancelled",
+            CancelReason::DeadlineExceeded => "context deadline exceeded",
+            CancelReason::ParentCancelled => "parent context cancelled",
+            CancelReason::ModeChange => "context cancelled by mode change",
+        })
+    }
+}
+
+impl From<CancelReason> for Error {
+    fn from(reason: CancelReason) -> Self {
+        Error::Custom(format!("{}", reason))
+    }
+}
+
+enum ContextState {
+    Starting,
+    Active(ContextData),
+    Cancelled(CancelReason),
+}
+
 struct ContextData {
     _parents: Vec<SetHandle<ByAddress<Arc<ContextValue>>, ContextHandle>>,
     event: Event,
@@ -171,7 +321,7 @@ impl Drop for ContextData {
     fn drop(&mut self) {
         self.event.notify();
         for child in self.children.iter() {
-            cancel(&child.1)
+            cancel(&child.state, CancelReason::ParentCancelled)
         }
     }
 }
@@ -183,7 +333,10 @@ impl OwnerMut<Event> for ContextHandle {
     where
         Event: 'a,
     {
-        Some(f(&mut self.0.upgrade()?.as_ref().1.lock().as_mut()?.event))
+        match &mut *self.0.upgrade()?.state.lock() {
+            ContextState::Active(data) => Some(f(&mut data.event)),
+            _ => None,
+        }
     }
 }
 
@@ -195,19 +348,23 @@ impl OwnerMut<Set<ByAddress<Arc<ContextValue>>>> for ContextHandle {
     where
         ContextValue: 'a,
     {
-        Some(f(&mut self
-            .0
-            .upgrade()?
-            .as_ref()
-            .1
-            .lock()
-            .as_mut()?
-            .children))
+        match &mut *self.0.upgrade()?.state.lock() {
+            ContextState::Active(data) => Some(f(&mut data.children)),
+            _ => None,
+        }
     }
 }
 
-fn cancel(m: &Mutex<Option<ContextData>>) {
-    m.lock().take();
+fn cancel(m: &Mutex<ContextState>, reason: CancelReason) {
+    let mut lock = m.lock();
+    if let ContextState::Cancelled(_) = *lock {
+        return;
+    }
+    let old = replace(&mut *lock, ContextState::Cancelled(reason));
+    // The old state is dropped after the lock is released, since dropping it
+    // cancels the children, which then detach themselves from this context.
+    drop(lock);
+    drop(old);
 }
 
 /// Provides a wrapper for [`Context`] objects which permits the management of
@@ -225,7 +382,7 @@ impl ContextWrapper {
     /// (which is returned).
     pub fn replace(&mut self) -> Context {
         if let Some(ctx) = self.0.take() {
-            ctx.cancel();
+            cancel(&ctx.0.state, CancelReason::ModeChange);
         }
         let ctx = Context::new_global();
         self.0 = Some(ctx.clone());
@@ -0,0 +1,221 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    cell::RefCell,
+    cmp::min,
+    future::Future,
+    marker::PhantomData,
+    mem::take,
+    pin::Pin,
+    task::{Context as TaskContext, Poll, RawWaker, RawWakerVTable, Waker},
+};
+use spin::once::Once;
+
+use super::{GenericSleep, Instant, Mutex, Promise, Selectable, Task};
+use crate::bindings;
+
+/// Deadlines reported by pending [`Selectable`] events while being polled as
+/// futures, keyed by the polling task; these let executors sleep until the
+/// earliest time an event could be ready, since wakers cannot carry a time.
+static DEADLINES: Once<Mutex<Vec<(Task, Instant)>>> = Once::new();
+
+#[inline]
+/// Creates a [`Future`] which resolves with the result of the given
+/// [`Selectable`] event.
+///
+/// Like any other event, the event notifies the task which created it; the
+/// future must therefore be polled from that task (e.g., by an [`Executor`]
+/// running on it).
+pub fn selectable_future<T>(event: impl Selectable<T>) -> impl Future<Output = T> {
+    struct SelectableFuture<T, E: Selectable<T>>(Option<E>, PhantomData<T>);
+
+    // The event is never pinned, so it is safe to move it in and out of the
+    // future.
+    impl<T, E: Selectable<T>> Unpin for SelectableFuture<T, E> {}
+
+    impl<T, E: Selectable<T>> Future for SelectableFuture<T, E> {
+        type Output = T;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut TaskContext<'_>) -> Poll<T> {
+            let this = self.get_mut();
+            let event = this.0.take().expect("future polled after completion");
+            match event.poll() {
+                Ok(r) => Poll::Ready(r),
+                Err(event) => {
+                    if let Some(t) = event.sleep().timeout() {
+                        register_deadline(t);
+                    }
+                    this.0 = Some(event);
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    SelectableFuture(Some(event), PhantomData)
+}
+
+#[inline]
+/// Creates a new [`Selectable`] event which occurs when the given [`Future`]
+/// resolves, with its result.
+///
+/// The future is polled by whichever task selects on the event.
+pub fn select_future<'a, T: 'a>(future: impl Future<Output = T> + 'a) -> impl Selectable<T> + 'a {
+    struct FutureSelect<F: Future> {
+        future: Pin<Box<F>>,
+        sleep: GenericSleep,
+    }
+
+    impl<F: Future> Selectable<F::Output> for FutureSelect<F> {
+        fn poll(mut self) -> Result<F::Output, Self> {
+            let waker = task_waker(&Task::current());
+            let mut cx = TaskContext::from_waker(&waker);
+            let (r, deadline) = deadline_scope(|| self.future.as_mut().poll(&mut cx));
+            match r {
+                Poll::Ready(r) => Ok(r),
+                Poll::Pending => {
+                    self.sleep = GenericSleep::NotifyTake(deadline);
+                    Err(self)
+                }
+            }
+        }
+
+        #[inline]
+        fn sleep(&self) -> GenericSleep {
+            self.sleep
+        }
+    }
+
+    FutureSelect {
+        future: Box::pin(future),
+        // The future has not been polled yet, so it could be ready right away.
+        sleep: GenericSleep::Timestamp(Instant::from_micros(0)),
+    }
+}
+
+/// A single-task executor for futures.
+///
+/// The executor runs on the task which created it: futures spawned via
+/// [`Executor::spawn_local()`] make progress whenever that task is inside
+/// [`Executor::block_on()`]. Wakers notify the executor's task, and any
+/// [`Selectable`] events awaited via [`selectable_future()`] notify it
+/// directly, so the executor sleeps whenever no future can make progress.
+pub struct Executor {
+    task: Task,
+    spawned: RefCell<Vec<Pin<Box<dyn Future<Output = ()>>>>>,
+}
+
+impl Executor {
+    #[inline]
+    /// Creates a new executor for the current task.
+    pub fn new() -> Self {
+        Self {
+            task: Task::current(),
+            spawned: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Spawns a future onto the executor, returning a [`Promise`] which
+    /// resolves with its result.
+    pub fn spawn_local<T: 'static>(&self, future: impl Future<Output = T> + 'static) -> Promise<T> {
+        let (promise, resolve) = Promise::new();
+        self.spawned
+            .borrow_mut()
+            .push(Box::pin(async move { resolve(future.await) }));
+        // Make sure the new future is polled promptly.
+        self.task.wake();
+        promise
+    }
+
+    /// Runs the executor until the given future resolves, returning its
+    /// result. Spawned futures make progress in the meantime.
+    pub fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+        let waker = task_waker(&self.task);
+        let mut cx = TaskContext::from_waker(&waker);
+        let mut future = Box::pin(future);
+        loop {
+            let (r, deadline) = deadline_scope(|| match future.as_mut().poll(&mut cx) {
+                Poll::Ready(r) => Some(r),
+                Poll::Pending => {
+                    self.poll_spawned(&mut cx);
+                    None
+                }
+            });
+            if let Some(r) = r {
+                return r;
+            }
+            GenericSleep::NotifyTake(deadline).sleep();
+        }
+    }
+
+    fn poll_spawned(&self, cx: &mut TaskContext<'_>) {
+        // Spawned futures may spawn further futures, so the list must not be
+        // borrowed while they are polled.
+        let mut spawned = take(&mut *self.spawned.borrow_mut());
+        spawned.retain_mut(|f| f.as_mut().poll(cx).is_pending());
+        self.spawned.borrow_mut().append(&mut spawned);
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline]
+/// Runs a future to completion on the current task, returning its result; see
+/// [`Executor::block_on()`].
+pub fn block_on<T>(future: impl Future<Output = T>) -> T {
+    Executor::new().block_on(future)
+}
+
+const WAKER_VTABLE: RawWakerVTable =
+    RawWakerVTable::new(waker_clone, waker_wake, waker_wake, waker_drop);
+
+#[inline]
+fn task_waker(task: &Task) -> Waker {
+    unsafe { Waker::from_raw(RawWaker::new(task.0 as *const (), &WAKER_VTABLE)) }
+}
+
+unsafe fn waker_clone(data: *const ()) -> RawWaker {
+    RawWaker::new(data, &WAKER_VTABLE)
+}
+
+unsafe fn waker_wake(data: *const ()) {
+    Task(data as bindings::task_t).wake();
+}
+
+unsafe fn waker_drop(_: *const ()) {}
+
+#[inline]
+fn deadlines() -> &'static Mutex<Vec<(Task, Instant)>> {
+    DEADLINES.call_once(|| Mutex::new(Vec::new()))
+}
+
+fn register_deadline(deadline: Instant) {
+    let task = Task::current();
+    let mut lock = deadlines().lock();
+    match lock.iter_mut().find(|(t, _)| *t == task) {
+        Some((_, d)) => *d = min(*d, deadline),
+        None => lock.push((task, deadline)),
+    }
+}
+
+fn take_deadline() -> Option<Instant> {
+    let task = Task::current();
+    let mut lock = deadlines().lock();
+    let index = lock.iter().position(|(t, _)| *t == task)?;
+    Some(lock.swap_remove(index).1)
+}
+
+/// Runs `f`, returning its result along with the earliest deadline registered
+/// while it ran. Deadlines registered beforehand are preserved.
+fn deadline_scope<R>(f: impl FnOnce() -> R) -> (R, Option<Instant>) {
+    let outer = take_deadline();
+    let r = f();
+    let inner = take_deadline();
+    if let Some(d) = outer {
+        register_deadline(d);
+    }
+    (r, inner)
+}
@@ -0,0 +1,202 @@
+use alloc::{collections::VecDeque, format};
+use core::fmt::{self, Display, Formatter};
+use owner_monad::OwnerMut;
+
+use super::{handle_event, Event, EventHandle, GenericSleep, Instant, Mutex, Selectable};
+use crate::error::Error;
+
+/// Represents a source of data which keeps a bounded history of published
+/// values, so that each listener receives every value rather than only the
+/// latest one.
+///
+/// # Lag
+///
+/// Up to `capacity` values are kept. A listener which falls further behind
+/// than that misses the oldest values; rather than skipping them silently, it
+/// is told how many were missed (see [`Lagged`]) and then continues from the
+/// oldest value still kept.
+pub struct HistoryBroadcast<T: Clone>(Mutex<HistoryData<T>>);
+
+impl<T: Clone> HistoryBroadcast<T> {
+    #[inline]
+    /// Creates a new history broadcast event which keeps up to `capacity`
+    /// values. Panics on failure; see [`HistoryBroadcast::try_new()`].
+    pub fn new(capacity: usize) -> Self {
+        Self::try_new(capacity)
+            .unwrap_or_else(|err| panic!("failed to create history broadcast: {:?}", err))
+    }
+
+    /// Creates a new history broadcast event which keeps up to `capacity`
+    /// values. Fails if `capacity` is zero.
+    pub fn try_new(capacity: usize) -> Result<Self, Error> {
+        if capacity == 0 {
+            return Err(Error::Custom(
+                "history broadcast capacity must be nonzero".into(),
+            ));
+        }
+        Ok(Self(Mutex::try_new(HistoryData {
+            buffer: VecDeque::with_capacity(capacity),
+            capacity,
+            start: 0,
+            event: Event::new(),
+        })?))
+    }
+
+    #[inline]
+    /// Gets the number of values kept by the broadcast event.
+    pub fn capacity(&self) -> usize {
+        self.0.lock().capacity
+    }
+
+    /// Gets a copy of the most recently published value, if any.
+    pub fn latest(&self) -> Option<T> {
+        self.0.lock().buffer.back().cloned()
+    }
+
+    #[inline]
+    /// Creates a new listener for the broadcast event, which receives values
+    /// published from now on.
+    pub fn listen(&self) -> HistoryListener<'_, T> {
+        let next = self.0.lock().end();
+        HistoryListener { next, mtx: &self.0 }
+    }
+
+    #[inline]
+    /// Creates a new listener for the broadcast event, which receives every
+    /// value which is still kept, followed by values published from now on.
+    pub fn listen_from_oldest(&self) -> HistoryListener<'_, T> {
+        let next = self.0.lock().start;
+        HistoryListener { next, mtx: &self.0 }
+    }
+
+    /// Publishes a new value for the broadcast event, discarding the oldest
+    /// value if the history is full.
+    pub fn publish(&self, data: T) {
+        let mut lock = self.0.lock();
+        if lock.buffer.len() == lock.capacity {
+            lock.buffer.pop_front();
+            lock.start += 1;
+        }
+        lock.buffer.push_back(data);
+        lock.event.notify();
+    }
+}
+
+/// Provides a means of listening to every update from a [`HistoryBroadcast`]
+/// event.
+pub struct HistoryListener<'a, T: Clone> {
+    next: u64,
+    mtx: &'a Mutex<HistoryData<T>>,
+}
+
+impl<'a, T: Clone> HistoryListener<'a, T> {
+    #[inline]
+    /// Gets the next unprocessed value from the event, if there is one.
+    ///
+    /// If values were discarded before the listener received them, returns
+    /// [`Lagged`] with the number of values missed; the following call
+    /// returns the oldest value still kept.
+    pub fn next_value(&mut self) -> Option<Result<T, Lagged>> {
+        Self::next_value_impl(&mut self.next, self.mtx)
+    }
+
+    #[inline]
+    /// Gets the number of values which are waiting to be received by the
+    /// listener, not counting any which were missed.
+    pub fn pending(&self) -> usize {
+        let lock = self.mtx.lock();
+        (lock.end() - self.next.max(lock.start)) as usize
+    }
+
+    #[inline]
+    /// A [`Selectable`] event which occurs when there is a value for the
+    /// listener to receive, or when it has lagged; see
+    /// [`next_value()`](Self::next_value()).
+    pub fn select(&'_ mut self) -> impl Selectable<Result<T, Lagged>> + '_ {
+        struct HistorySelect<'b, T: Clone> {
+            next: &'b mut u64,
+            mtx: &'b Mutex<HistoryData<T>>,
+            _handle: EventHandle<&'b Mutex<HistoryData<T>>>,
+        }
+
+        impl<'b, T: Clone> Selectable<Result<T, Lagged>> for HistorySelect<'b, T> {
+            #[inline]
+            fn poll(self) -> Result<Result<T, Lagged>, Self> {
+                HistoryListener::next_value_impl(self.next, self.mtx).ok_or(self)
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if *self.next < self.mtx.lock().end() {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                } else {
+                    GenericSleep::NotifyTake(None)
+                }
+            }
+        }
+
+        let mtx: &'_ Mutex<HistoryData<T>> = self.mtx;
+
+        HistorySelect {
+            next: &mut self.next,
+            mtx,
+            _handle: handle_event(mtx),
+        }
+    }
+
+    fn next_value_impl(next: &mut u64, mtx: &Mutex<HistoryData<T>>) -> Option<Result<T, Lagged>> {
+        let lock = mtx.lock();
+        if *next < lock.start {
+            let missed = lock.start - *next;
+            *next = lock.start;
+            Some(Err(Lagged(missed)))
+        } else if *next < lock.end() {
+            let value = lock.buffer[(*next - lock.start) as usize].clone();
+            *next += 1;
+            Some(Ok(value))
+        } else {
+            None
+        }
+    }
+}
+
+/// Indicates that a [`HistoryListener`] fell behind and missed values which
+/// were discarded from the history. Wraps the number of values missed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lagged(pub u64);
+
+impl Display for Lagged {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "listener lagged by {} values", self.0)
+    }
+}
+
+impl From<Lagged> for Error {
+    fn from(lagged: Lagged) -> Self {
+        Error::Custom(format!("{}", lagged))
+    }
+}
+
+impl<T> OwnerMut<Event> for &Mutex<HistoryData<T>> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
+
+struct HistoryData<T> {
+    buffer: VecDeque<T>,
+    capacity: usize,
+    /// The sequence number of the oldest value in the buffer.
+    start: u64,
+    event: Event,
+}
+
+impl<T> HistoryData<T> {
+    #[inline]
+    /// The sequence number of the next value to be published.
+    fn end(&self) -> u64 {
+        self.start + self.buffer.len() as u64
+    }
+}
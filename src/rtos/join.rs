@@ -0,0 +1,125 @@
+use alloc::{boxed::Box, sync::Arc};
+
+use super::{select_map, Context, Mutex, Promise, Selectable, Task};
+use crate::error::Error;
+
+type Resolver<T> = Arc<Mutex<Option<Box<dyn FnOnce(Option<T>) + Send>>>>;
+
+/// An owned handle to a task spawned via [`Task::spawn_join()`] or
+/// [`Task::spawn_join_ext()`], which can be used to await its result.
+///
+/// The task is given a [`Context`], which is cancelled when the handle is
+/// dropped (or via [`JoinHandle::cancel()`]); the task is expected to observe
+/// the context and return promptly once it is cancelled.
+pub struct JoinHandle<T: 'static> {
+    task: Task,
+    promise: Promise<Option<T>>,
+    resolve: Resolver<T>,
+    ctx: Context,
+}
+
+impl Task {
+    #[inline]
+    /// Spawns a new task with no name and the default priority and stack depth,
+    /// returning a [`JoinHandle`] for its result.
+    pub fn spawn_join<T, F>(f: F) -> Result<JoinHandle<T>, Error>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(Context) -> T + Send + 'static,
+    {
+        Self::spawn_join_ext("", Self::DEFAULT_PRIORITY, Self::DEFAULT_STACK_DEPTH, f)
+    }
+
+    /// Spawns a new task with the specified name, priority and stack depth,
+    /// returning a [`JoinHandle`] for its result.
+    pub fn spawn_join_ext<T, F>(
+        name: &str,
+        priority: u32,
+        stack_depth: u16,
+        f: F,
+    ) -> Result<JoinHandle<T>, Error>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(Context) -> T + Send + 'static,
+    {
+        let ctx = Context::new_global();
+        let (promise, resolve) = Promise::new();
+        let resolve: Box<dyn FnOnce(Option<T>) + Send> = Box::new(resolve);
+        let resolve: Resolver<T> = Arc::new(Mutex::try_new(Some(resolve))?);
+        let task_ctx = ctx.clone();
+        let task_resolve = resolve.clone();
+        let task = Self::spawn_ext(name, priority, stack_depth, move || {
+            let r = f(task_ctx);
+            // The lock is held while resolving, so that the task cannot be
+            // deleted part-way through.
+            if let Some(resolve) = task_resolve.lock().take() {
+                resolve(Some(r));
+            }
+        })?;
+        Ok(JoinHandle {
+            task,
+            promise,
+            resolve,
+            ctx,
+        })
+    }
+}
+
+impl<T: 'static> JoinHandle<T> {
+    #[inline]
+    /// Gets the task which is running.
+    pub fn task(&self) -> &Task {
+        &self.task
+    }
+
+    #[inline]
+    /// Gets the context which was given to the task.
+    pub fn context(&self) -> &Context {
+        &self.ctx
+    }
+
+    #[inline]
+    /// Cancels the context which was given to the task.
+    pub fn cancel(&self) {
+        self.ctx.cancel();
+    }
+
+    /// Deletes the task, if it has not already finished; it then finishes
+    /// without a result. See [`Task::delete()`].
+    ///
+    /// # Safety
+    ///
+    /// This is unsafe for the same reasons as [`Task::delete()`]. Note that
+    /// deleting the task via [`Task::delete()`] directly, rather than through
+    /// this function, means that it never finishes.
+    pub unsafe fn delete(&self) {
+        let mut lock = self.resolve.lock();
+        if let Some(resolve) = lock.take() {
+            self.task.delete();
+            resolve(None);
+        }
+    }
+
+    #[inline]
+    /// A [`Selectable`] event which occurs when the task finishes.
+    ///
+    /// The result is the return value of the task if it exited normally, or
+    /// [`None`] if it was deleted via [`JoinHandle::delete()`] before
+    /// returning.
+    pub fn done(&'_ self) -> impl Selectable<Option<&'_ T>> + '_ {
+        select_map(self.promise.done(), Option::as_ref)
+    }
+
+    #[inline]
+    /// Returns `true` if the task has finished, whether or not it exited
+    /// normally.
+    pub fn is_finished(&self) -> bool {
+        self.done().poll().is_ok()
+    }
+}
+
+impl<T: 'static> Drop for JoinHandle<T> {
+    fn drop(&mut self) {
+        self.ctx.cancel();
+    }
+}
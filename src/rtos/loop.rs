@@ -1,12 +1,30 @@
-use core::time::Duration;
+use alloc::boxed::Box;
+use core::{cmp::max, time::Duration};
 
-use super::{time_since_start, GenericSleep, Instant, Selectable, Task};
+use super::{time_since_start, GenericSleep, Instant, Mutex, Selectable, Task};
+
+type OverrunCallback = Box<dyn FnMut(u32) + Send>;
 
 /// Provides a constant-period looping construct.
+///
+/// # Overruns
+///
+/// A cycle overruns when its work takes long enough that one or more of the
+/// following cycle start times pass before the loop waits again. Each such
+/// start time counts as a missed cycle in the loop's [`LoopStats`], and is
+/// reported to the overrun callback (see [`Loop::on_overrun()`]), if any. What
+/// happens next is determined by the loop's [`OverrunPolicy`].
 pub struct Loop {
     delta: Duration,
     next: Instant,
     cycle: usize,
+    policy: OverrunPolicy,
+    stats: LoopStats,
+    last_wake: Option<Instant>,
+    missed_until: Option<Instant>,
+    // Behind a mutex so that the loop remains `Sync`; it is only ever locked
+    // through `&mut self`, so the lock is never contended.
+    on_overrun: Option<Mutex<OverrunCallback>>,
 }
 
 impl Loop {
@@ -17,16 +35,45 @@ impl Loop {
             delta,
             next: time_since_start() + delta,
             cycle: 0,
+            policy: OverrunPolicy::CatchUp,
+            stats: LoopStats::default(),
+            last_wake: None,
+            missed_until: None,
+            on_overrun: None,
+        }
+    }
+
+    #[inline]
+    /// Creates a new loop object with a given period and overrun policy.
+    pub fn with_policy(delta: Duration, policy: OverrunPolicy) -> Self {
+        Loop {
+            policy,
+            ..Self::new(delta)
         }
     }
 
+    #[inline]
+    /// Sets the overrun policy of the loop.
+    pub fn set_policy(&mut self, policy: OverrunPolicy) {
+        self.policy = policy;
+    }
+
+    #[inline]
+    /// Sets a callback which is run whenever cycles are missed, with the
+    /// number of cycles which were missed. The callback runs on the looping
+    /// task, before the overrun policy is applied. Panics on failure to create
+    /// the mutex which guards the callback.
+    pub fn on_overrun(&mut self, callback: impl FnMut(u32) + Send + 'static) {
+        self.on_overrun = Some(Mutex::new(Box::new(callback)));
+    }
+
     /// Delays until the next loop cycle.
     pub fn delay(&mut self) {
+        self.begin_wait();
         if let Some(d) = self.next.checked_sub_instant(time_since_start()) {
             Task::delay(d);
         }
-        self.next += self.delta;
-        self.cycle += 1;
+        self.end_wait();
     }
 
     #[inline]
@@ -34,7 +81,8 @@ impl Loop {
     ///
     /// This is initially 0 and increments each cycle (i.e., when
     /// [`delay()`](Self::delay()) returns or `select` successfully
-    /// completes).
+    /// completes). Under [`OverrunPolicy::Skip`], it also counts the cycles
+    /// which were skipped.
     pub fn cycle(&self) -> usize {
         self.cycle
     }
@@ -46,6 +94,18 @@ impl Loop {
         self.cycle % modulus == 0
     }
 
+    #[inline]
+    /// Gets the overrun statistics of the loop.
+    pub fn stats(&self) -> &LoopStats {
+        &self.stats
+    }
+
+    #[inline]
+    /// Resets the overrun statistics of the loop.
+    pub fn reset_stats(&mut self) {
+        self.stats = LoopStats::default();
+    }
+
     #[inline]
     /// A [`Selectable`] event which occurs at the next loop cycle.
     pub fn select(&'_ mut self) -> impl Selectable + '_ {
@@ -54,8 +114,7 @@ impl Loop {
         impl<'a> Selectable for LoopSelect<'a> {
             fn poll(self) -> Result<(), Self> {
                 if time_since_start() >= self.0.next {
-                    self.0.next += self.0.delta;
-                    self.0.cycle += 1;
+                    self.0.end_wait();
                     Ok(())
                 } else {
                     Err(self)
@@ -66,6 +125,125 @@ impl Loop {
             }
         }
 
+        self.begin_wait();
         LoopSelect(self)
     }
+
+    /// Records the end of a cycle's work, detecting and handling overruns.
+    fn begin_wait(&mut self) {
+        let now = time_since_start();
+        if let Some(wake) = self.last_wake {
+            self.stats.worst_execution = max(self.stats.worst_execution, now - wake);
+        }
+
+        // Only count start times which have not already been counted, since
+        // the loop may wait more than once per cycle, and catching up runs
+        // late cycles back-to-back.
+        let mut first = self.next + self.delta;
+        if let Some(until) = self.missed_until {
+            if until >= first {
+                first = until + self.delta;
+            }
+        }
+        if now < first {
+            return;
+        }
+        let missed = ((now - first).as_micros() / self.delta.as_micros().max(1)) as u32 + 1;
+        self.missed_until = Some(first + self.delta * (missed - 1));
+        self.stats.missed_cycles += missed as u64;
+        if let Some(callback) = &self.on_overrun {
+            (callback.lock())(missed);
+        }
+
+        match self.policy {
+            OverrunPolicy::CatchUp => {}
+            OverrunPolicy::Skip => {
+                self.next += self.delta * missed;
+                self.cycle += missed as usize;
+            }
+            OverrunPolicy::ResetPhase => {
+                self.next = now;
+                self.missed_until = None;
+            }
+        }
+    }
+
+    /// Records the start of a new cycle.
+    fn end_wait(&mut self) {
+        let now = time_since_start();
+        // Delays have millisecond precision, so the cycle may start slightly
+        // early.
+        self.stats
+            .record_jitter(now.checked_sub_instant(self.next).unwrap_or_default());
+        self.last_wake = Some(now);
+        self.next += self.delta;
+        self.cycle += 1;
+    }
+}
+
+/// Determines how a [`Loop`] responds to missed cycles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverrunPolicy {
+    /// Run the missed cycles back-to-back until the loop is back on schedule.
+    /// This is the default.
+    CatchUp,
+    /// Skip the missed cycles, running the next cycle immediately and then
+    /// continuing on the original schedule.
+    Skip,
+    /// Run the next cycle immediately and schedule the following cycles
+    /// relative to it.
+    ResetPhase,
+}
+
+impl Default for OverrunPolicy {
+    fn default() -> Self {
+        Self::CatchUp
+    }
+}
+
+/// Overrun statistics for a [`Loop`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LoopStats {
+    missed_cycles: u64,
+    max_jitter: Duration,
+    total_jitter: Duration,
+    samples: u32,
+    worst_execution: Duration,
+}
+
+impl LoopStats {
+    #[inline]
+    /// Gets the total number of cycles which were missed.
+    pub fn missed_cycles(&self) -> u64 {
+        self.missed_cycles
+    }
+
+    #[inline]
+    /// Gets the largest delay between the scheduled start of a cycle and the
+    /// time it actually started.
+    pub fn max_jitter(&self) -> Duration {
+        self.max_jitter
+    }
+
+    #[inline]
+    /// Gets the average delay between the scheduled start of a cycle and the
+    /// time it actually started.
+    pub fn avg_jitter(&self) -> Duration {
+        self.total_jitter
+            .checked_div(self.samples)
+            .unwrap_or(Duration::ZERO)
+    }
+
+    #[inline]
+    /// Gets the longest time which a cycle spent between starting and
+    /// waiting for the next cycle.
+    pub fn worst_execution(&self) -> Duration {
+        self.worst_execution
+    }
+
+    fn record_jitter(&mut self, jitter: Duration) {
+        self.max_jitter = max(self.max_jitter, jitter);
+        self.total_jitter += jitter;
+        self.samples += 1;
+    }
 }
@@ -1,6 +1,6 @@
 //! Multitasking primitives.
 
-use alloc::{boxed::Box, format, string::String};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
 use core::{
     cmp::min,
     convert::TryInto,
@@ -220,12 +220,22 @@ pub fn time_since_start() -> Instant {
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
 /// Represents a FreeRTOS task.
+///
+/// PROS declares neither the stack high-water mark of a task nor a way to
+/// enumerate all tasks in its public headers, so neither is available here;
+/// [`Task::count()`] and [`Task::state()`] are the closest equivalents.
 pub struct Task(bindings::task_t);
 
 impl Task {
     /// The default priority for new tasks.
     pub const DEFAULT_PRIORITY: u32 = bindings::TASK_PRIORITY_DEFAULT;
 
+    /// The lowest priority a task can have.
+    pub const MIN_PRIORITY: u32 = bindings::TASK_PRIORITY_MIN;
+
+    /// The highest priority a task can have.
+    pub const MAX_PRIORITY: u32 = bindings::TASK_PRIORITY_MAX;
+
     /// The default stack depth for new tasks.
     pub const DEFAULT_STACK_DEPTH: u16 = bindings::TASK_STACK_DEPTH_DEFAULT as u16;
 
@@ -321,22 +331,50 @@ impl Task {
         unsafe { bindings::task_get_priority(self.0) }
     }
 
+    #[inline]
+    /// Sets the priority of the task.
+    ///
+    /// The priority is clamped by the kernel to the range from
+    /// [`MIN_PRIORITY`](Self::MIN_PRIORITY) to
+    /// [`MAX_PRIORITY`](Self::MAX_PRIORITY), inclusive.
+    pub fn set_priority(&self, priority: u32) {
+        unsafe { bindings::task_set_priority(self.0, priority) }
+    }
+
     #[inline]
     /// Gets the state of the task.
     pub fn state(&self) -> TaskState {
         match unsafe { bindings::task_get_state(self.0) } {
-            bindings::task_state_e_t_E_TASK_STATE_RUNNING => TaskState::Running,
-            bindings::task_state_e_t_E_TASK_STATE_READY => TaskState::Ready,
-            bindings::task_state_e_t_E_TASK_STATE_BLOCKED => TaskState::Blocked,
-            bindings::task_state_e_t_E_TASK_STATE_SUSPENDED => TaskState::Suspended,
-            bindings::task_state_e_t_E_TASK_STATE_DELETED => TaskState::Deleted,
             bindings::task_state_e_t_E_TASK_STATE_INVALID => {
                 panic!("invalid task handle: {:#010x}", self.0 as usize)
             }
-            x => panic!("bindings::task_get_state returned unexpected value: {}", x),
+            x => TaskState::from_raw(x),
         }
     }
 
+    #[inline]
+    /// Suspends the task, so that it is not scheduled until it is resumed via
+    /// [`Task::resume()`].
+    ///
+    /// Suspending a task which holds a lock will block any other task which
+    /// needs that lock.
+    pub fn suspend(&self) {
+        unsafe { bindings::task_suspend(self.0) }
+    }
+
+    #[inline]
+    /// Resumes the task after it was suspended via [`Task::suspend()`].
+    pub fn resume(&self) {
+        unsafe { bindings::task_resume(self.0) }
+    }
+
+    #[inline]
+    /// Gets the number of tasks which the kernel is currently managing,
+    /// including tasks which have been deleted but not yet cleaned up.
+    pub fn count() -> u32 {
+        unsafe { bindings::task_get_count() }
+    }
+
     #[inline]
     /// Unsafely deletes the task.
     ///
@@ -364,6 +402,7 @@ unsafe impl Send for Task {}
 unsafe impl Sync for Task {}
 
 /// Represents the state of a [`Task`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TaskState {
     /// The task is actively executing.
     Running,
@@ -377,6 +416,19 @@ pub enum TaskState {
     Deleted,
 }
 
+impl TaskState {
+    fn from_raw(state: bindings::task_state_e_t) -> Self {
+        match state {
+            bindings::task_state_e_t_E_TASK_STATE_RUNNING => TaskState::Running,
+            bindings::task_state_e_t_E_TASK_STATE_READY => TaskState::Ready,
+            bindings::task_state_e_t_E_TASK_STATE_BLOCKED => TaskState::Blocked,
+            bindings::task_state_e_t_E_TASK_STATE_SUSPENDED => TaskState::Suspended,
+            bindings::task_state_e_t_E_TASK_STATE_DELETED => TaskState::Deleted,
+            x => panic!("unexpected task state: {}", x),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 /// Represents a future time to sleep until.
 pub enum GenericSleep {
@@ -536,6 +588,202 @@ pub fn select_option<'a, T: 'a>(base: Option<impl Selectable<T> + 'a>) -> impl S
     OptionSelect(base, PhantomData)
 }
 
+#[inline]
+/// Creates a new [`Selectable`] event which processes exactly one of the given
+/// events, the number of which need not be known at compile time. The result
+/// is the index of the event which was processed, along with its result.
+///
+/// If `events` is empty, the resulting event never occurs.
+pub fn select_vec<'a, T: 'a, E: Selectable<T> + 'a>(
+    events: Vec<E>,
+) -> impl Selectable<(usize, T)> + 'a {
+    struct VecSelect<T, E: Selectable<T>>(Vec<E>, PhantomData<T>);
+
+    impl<T, E: Selectable<T>> Selectable<(usize, T)> for VecSelect<T, E> {
+        fn poll(self) -> Result<(usize, T), Self> {
+            let mut events = Vec::with_capacity(self.0.len());
+            for (i, event) in self.0.into_iter().enumerate() {
+                match event.poll() {
+                    Ok(r) => return Ok((i, r)),
+                    Err(e) => events.push(e),
+                }
+            }
+            Err(Self(events, PhantomData))
+        }
+
+        fn sleep(&self) -> GenericSleep {
+            self.0
+                .iter()
+                .map(Selectable::sleep)
+                .reduce(GenericSleep::combine)
+                .unwrap_or(GenericSleep::NotifyTake(None))
+        }
+    }
+
+    VecSelect(events, PhantomData)
+}
+
+#[inline]
+/// Creates a new [`Selectable`] event which processes exactly one of the
+/// events from the given iterator; see [`select_vec()`].
+pub fn select_iter<'a, T: 'a, E: Selectable<T> + 'a>(
+    events: impl IntoIterator<Item = E>,
+) -> impl Selectable<(usize, T)> + 'a {
+    select_vec(events.into_iter().collect())
+}
+
+#[inline]
+/// Creates a new [`Selectable`] event which occurs once all of the given
+/// events have been processed. The result contains the results of the events,
+/// in the same order as the events themselves.
+///
+/// If `events` is empty, the resulting event occurs immediately.
+pub fn join_all<'a, T: 'a, E: Selectable<T> + 'a>(
+    events: impl IntoIterator<Item = E>,
+) -> impl Selectable<Vec<T>> + 'a {
+    struct JoinSelect<T, E: Selectable<T>>(Vec<Result<T, E>>);
+
+    impl<T, E: Selectable<T>> Selectable<Vec<T>> for JoinSelect<T, E> {
+        fn poll(self) -> Result<Vec<T>, Self> {
+            let events: Vec<Result<T, E>> = self
+                .0
+                .into_iter()
+                .map(|e| e.or_else(Selectable::poll))
+                .collect();
+            if events.iter().all(Result::is_ok) {
+                Ok(events.into_iter().filter_map(Result::ok).collect())
+            } else {
+                Err(Self(events))
+            }
+        }
+
+        fn sleep(&self) -> GenericSleep {
+            self.0
+                .iter()
+                .filter_map(|e| e.as_ref().err())
+                .map(Selectable::sleep)
+                .reduce(GenericSleep::combine)
+                .unwrap_or_else(|| GenericSleep::Timestamp(Instant::from_millis(0)))
+        }
+    }
+
+    JoinSelect(events.into_iter().map(Err).collect())
+}
+
+/// The error produced when an event wrapped via [`with_timeout()`] or
+/// [`with_deadline()`] does not occur in time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timeout;
+
+impl Display for Timeout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("timed out")
+    }
+}
+
+impl From<Timeout> for Error {
+    fn from(_: Timeout) -> Self {
+        Error::Custom("timed out".into())
+    }
+}
+
+#[inline]
+/// Creates a new [`Selectable`] event which processes the given event if it
+/// occurs within `timeout`, or otherwise fails with [`Timeout`].
+pub fn with_timeout<'a, T: 'a>(
+    event: impl Selectable<T> + 'a,
+    timeout: Duration,
+) -> impl Selectable<Result<T, Timeout>> + 'a {
+    with_deadline(event, time_since_start() + timeout)
+}
+
+#[inline]
+/// Creates a new [`Selectable`] event which processes the given event if it
+/// occurs by `deadline`, or otherwise fails with [`Timeout`].
+pub fn with_deadline<'a, T: 'a>(
+    event: impl Selectable<T> + 'a,
+    deadline: Instant,
+) -> impl Selectable<Result<T, Timeout>> + 'a {
+    select_either(
+        select_map(event, Ok),
+        select_map(delay_until(deadline), |_| Err(Timeout)),
+    )
+}
+
+#[inline]
+/// Creates a new [`Selectable`] event which, once the given event is
+/// processed, continues with the event produced by passing its result to `f`.
+/// The result is that of the second event.
+pub fn select_then<'a, T: 'a, U: 'a, E: Selectable<U> + 'a>(
+    event: impl Selectable<T> + 'a,
+    f: impl FnOnce(T) -> E + 'a,
+) -> impl Selectable<U> + 'a {
+    enum ThenSelect<T, U, E1: Selectable<T>, E2: Selectable<U>, F: FnOnce(T) -> E2> {
+        First(E1, F, PhantomData<(T, U)>),
+        Second(E2),
+    }
+
+    impl<T, U, E1: Selectable<T>, E2: Selectable<U>, F: FnOnce(T) -> E2> Selectable<U>
+        for ThenSelect<T, U, E1, E2, F>
+    {
+        fn poll(self) -> Result<U, Self> {
+            let event = match self {
+                ThenSelect::First(event, f, _) => match event.poll() {
+                    Ok(r) => f(r),
+                    Err(event) => return Err(ThenSelect::First(event, f, PhantomData)),
+                },
+                ThenSelect::Second(event) => event,
+            };
+            event.poll().map_err(ThenSelect::Second)
+        }
+
+        fn sleep(&self) -> GenericSleep {
+            match self {
+                ThenSelect::First(event, _, _) => event.sleep(),
+                ThenSelect::Second(event) => event.sleep(),
+            }
+        }
+    }
+
+    ThenSelect::First(event, f, PhantomData)
+}
+
+#[inline]
+/// Creates a new [`Selectable`] event which, once the given event is
+/// processed, continues with the event produced by passing its result to `f`
+/// if it succeeded; an error is passed through immediately. This is useful for
+/// chaining fallible events, such as those created via [`with_timeout()`].
+pub fn select_ok<'a, T: 'a, U: 'a, Er: 'a, E: Selectable<Result<U, Er>> + 'a>(
+    event: impl Selectable<Result<T, Er>> + 'a,
+    f: impl FnOnce(T) -> E + 'a,
+) -> impl Selectable<Result<U, Er>> + 'a {
+    enum OkSelect<T, E: Selectable<T>> {
+        Pending(E),
+        Ready(T),
+    }
+
+    impl<T, E: Selectable<T>> Selectable<T> for OkSelect<T, E> {
+        fn poll(self) -> Result<T, Self> {
+            match self {
+                OkSelect::Pending(event) => event.poll().map_err(OkSelect::Pending),
+                OkSelect::Ready(r) => Ok(r),
+            }
+        }
+
+        fn sleep(&self) -> GenericSleep {
+            match self {
+                OkSelect::Pending(event) => event.sleep(),
+                OkSelect::Ready(_) => GenericSleep::Timestamp(Instant::from_millis(0)),
+            }
+        }
+    }
+
+    select_then(event, move |r| match r {
+        Ok(v) => OkSelect::Pending(f(v)),
+        Err(e) => OkSelect::Ready(Err(e)),
+    })
+}
+
 #[inline]
 /// Awaits a [`Selectable`] event.
 pub fn select<'a, T: 'a>(mut event: impl Selectable<T> + 'a) -> T {
@@ -576,22 +824,44 @@ pub fn delay_until(timestamp: Instant) -> impl Selectable {
     DelaySelect(timestamp)
 }
 
+mod barrier;
 mod broadcast;
 mod channel;
+mod condvar;
 mod context;
 mod event;
+mod future;
+mod history_broadcast;
+mod join;
 mod r#loop;
 mod mutex;
+mod notification;
+mod notify;
 mod promise;
 mod queue;
+mod rwlock;
+mod scope;
 mod semaphore;
+mod timer;
+mod wait_group;
 
+pub use barrier::*;
 pub use broadcast::*;
 pub use channel::*;
+pub use condvar::*;
 pub use context::*;
 pub use event::*;
+pub use future::*;
+pub use history_broadcast::*;
+pub use join::*;
 pub use mutex::*;
+pub use notification::*;
+pub use notify::*;
 pub use promise::*;
 pub use queue::*;
 pub use r#loop::*;
+pub use rwlock::*;
+pub use scope::*;
 pub use semaphore::*;
+pub use timer::*;
+pub use wait_group::*;
@@ -2,11 +2,41 @@ use core::{
     cell::UnsafeCell,
     fmt::{self, Debug, Display, Formatter},
     ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU32, Ordering},
+    time::Duration,
 };
 
-use crate::{bindings, error::*};
+use crate::{bindings, error::*, io::eprintln};
 
-use super::TIMEOUT_MAX;
+use super::{time_since_start, GenericSleep, Instant, Selectable, Task, Timeout, TIMEOUT_MAX};
+
+/// The mutex debugging threshold in milliseconds, or 0 if debugging is
+/// disabled.
+static DEBUG_THRESHOLD: AtomicU32 = AtomicU32::new(0);
+
+/// Enables or disables mutex debugging.
+///
+/// While debugging is enabled with a given `threshold`, a warning is printed
+/// whenever a [`Mutex`] is held for longer than `threshold`, and a task which
+/// blocks in [`Mutex::lock()`] or [`Mutex::try_lock()`] prints a warning,
+/// naming the task which holds the mutex, each time it has waited for a
+/// further `threshold`. This helps to track down deadlocks and long critical
+/// sections, at the cost of some overhead on every lock.
+pub fn set_mutex_debug(threshold: Option<Duration>) {
+    // A threshold of `TIMEOUT_MAX` would make each wait last forever.
+    let ms = threshold.map_or(0, |t| {
+        t.as_millis().clamp(1, (TIMEOUT_MAX - 1) as u128) as u32
+    });
+    DEBUG_THRESHOLD.store(ms, Ordering::Relaxed);
+}
+
+#[inline]
+fn debug_threshold() -> Option<u32> {
+    match DEBUG_THRESHOLD.load(Ordering::Relaxed) {
+        0 => None,
+        ms => Some(ms),
+    }
+}
 
 /// Represents an object which is protected by a FreeRTOS recursive mutex.
 pub struct Mutex<T: ?Sized> {
@@ -62,10 +92,47 @@ impl<T: ?Sized> Mutex<T> {
     /// mutex. Blocks until access can be obtained; see [`Mutex::lock()`] for a
     /// more thorough behavioural description.
     pub fn try_lock(&'_ self) -> Result<MutexGuard<'_, T>, Error> {
-        if unsafe { bindings::mutex_recursive_take(self.mutex, TIMEOUT_MAX) } {
-            Ok(MutexGuard(self))
+        let threshold = match debug_threshold() {
+            Some(threshold) => threshold,
+            None => {
+                return if unsafe { bindings::mutex_recursive_take(self.mutex, TIMEOUT_MAX) } {
+                    Ok(self.guard())
+                } else {
+                    Err(from_errno())
+                }
+            }
+        };
+
+        let start = time_since_start();
+        loop {
+            if unsafe { bindings::mutex_recursive_take(self.mutex, threshold) } {
+                return Ok(self.guard());
+            }
+            // The holder may have released the mutex since the take timed
+            // out, in which case the next take will likely succeed.
+            if let Some(owner) = self.owner() {
+                eprintln!(
+                    "warning: task '{}' has waited {:?} for mutex {:p} held by task '{}'",
+                    Task::current().name(),
+                    time_since_start() - start,
+                    self.mutex,
+                    owner.name()
+                );
+            }
+        }
+    }
+
+    #[inline]
+    /// Obtains a [`MutexGuard`] giving access to the object protected by the
+    /// mutex, blocking for up to `timeout` until access can be obtained. See
+    /// [`Mutex::lock()`] for a more thorough behavioural description.
+    pub fn lock_timeout(&'_ self, timeout: Duration) -> Result<MutexGuard<'_, T>, Timeout> {
+        // A timeout of `TIMEOUT_MAX` would wait forever.
+        let timeout = timeout.as_millis().min((TIMEOUT_MAX - 1) as u128) as u32;
+        if unsafe { bindings::mutex_recursive_take(self.mutex, timeout) } {
+            Ok(self.guard())
         } else {
-            Err(from_errno())
+            Err(Timeout)
         }
     }
 
@@ -74,11 +141,55 @@ impl<T: ?Sized> Mutex<T> {
     /// mutex, if it is available immediately. Does not block.
     pub fn poll(&'_ self) -> Option<MutexGuard<'_, T>> {
         if unsafe { bindings::mutex_recursive_take(self.mutex, 0) } {
-            Some(MutexGuard(self))
+            Some(self.guard())
         } else {
             None
         }
     }
+
+    /// A [`Selectable`] event which occurs when access to the object protected
+    /// by the mutex is obtained.
+    ///
+    /// Releasing a mutex does not notify waiting events, so while the mutex
+    /// is held by another task, the event is re-checked every millisecond.
+    /// Unlike [`Mutex::lock()`], waiting on the event does not lend the
+    /// current task's priority to the task holding the mutex.
+    pub fn lock_select(&'_ self) -> impl Selectable<MutexGuard<'_, T>> + '_ {
+        struct LockSelect<'a, T: ?Sized>(&'a Mutex<T>);
+
+        impl<'a, T: ?Sized> Selectable<MutexGuard<'a, T>> for LockSelect<'a, T> {
+            #[inline]
+            fn poll(self) -> Result<MutexGuard<'a, T>, Self> {
+                self.0.poll().ok_or(self)
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                match self.0.owner() {
+                    Some(owner) if owner != Task::current() => {
+                        GenericSleep::Timestamp(time_since_start() + Duration::from_millis(1))
+                    }
+                    _ => GenericSleep::Timestamp(Instant::from_millis(0)),
+                }
+            }
+        }
+
+        LockSelect(self)
+    }
+
+    /// Gets the task which currently holds the mutex, if any.
+    pub fn owner(&self) -> Option<Task> {
+        let task = unsafe { bindings::mutex_get_owner(self.mutex) };
+        if task.is_null() {
+            None
+        } else {
+            Some(Task(task))
+        }
+    }
+
+    #[inline]
+    fn guard(&'_ self) -> MutexGuard<'_, T> {
+        MutexGuard(self, debug_threshold().map(|_| time_since_start()))
+    }
 }
 
 impl<T: ?Sized> Drop for Mutex<T> {
@@ -134,7 +245,14 @@ impl<T> From<T> for Mutex<T> {
 /// out of scope. Rust's object and reference lifetime rules prevent safe code
 /// from retaining access to the [`Mutex`] object's internal data beyond the
 /// lifetime of the guard object.
-pub struct MutexGuard<'a, T: ?Sized>(&'a Mutex<T>);
+pub struct MutexGuard<'a, T: ?Sized>(&'a Mutex<T>, Option<Instant>);
+
+impl<'a, T: ?Sized> MutexGuard<'a, T> {
+    #[inline]
+    pub(crate) fn mutex(guard: &Self) -> &'a Mutex<T> {
+        guard.0
+    }
+}
 
 impl<T: ?Sized> Deref for MutexGuard<'_, T> {
     type Target = T;
@@ -158,6 +276,17 @@ impl<T: ?Sized> Drop for MutexGuard<'_, T> {
         if !unsafe { bindings::mutex_recursive_give(self.0.mutex) } {
             panic!("failed to return mutex: {:?}", from_errno());
         }
+        if let (Some(acquired), Some(threshold)) = (self.1, debug_threshold()) {
+            let held = time_since_start() - acquired;
+            if held > Duration::from_millis(threshold as u64) {
+                eprintln!(
+                    "warning: task '{}' held mutex {:p} for {:?}",
+                    Task::current().name(),
+                    self.0.mutex,
+                    held
+                );
+            }
+        }
     }
 }
 
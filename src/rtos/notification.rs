@@ -0,0 +1,229 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+use spin::once::Once;
+
+use super::{select, with_timeout, GenericSleep, Instant, Mutex, Selectable, Task};
+use crate::bindings;
+
+/// The notification state of each task which has been notified via
+/// [`Task::notify_ext()`] and has not yet received everything.
+///
+/// The kernel's own notification value is used by [`Event`](super::Event) to
+/// wake tasks waiting in a select, and is cleared every time such a task
+/// sleeps; the values are therefore kept here instead, and the kernel
+/// notification is only used to wake the receiving task.
+static NOTIFICATIONS: Once<Mutex<Vec<Notification>>> = Once::new();
+
+/// Represents the action to perform on the notification value of a [`Task`]
+/// when it is notified via [`Task::notify_ext()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotifyAction {
+    /// Leave the notification value unchanged.
+    None,
+    /// Set the given bits in the notification value.
+    SetBits,
+    /// Increment the notification value; the given value is ignored.
+    Increment,
+    /// Overwrite the notification value with the given value.
+    Overwrite,
+    /// Overwrite the notification value with the given value, but only if the
+    /// task has no pending notification.
+    NoOverwrite,
+}
+
+impl Task {
+    #[inline]
+    /// Notifies the task, incrementing its notification value.
+    pub fn notify(&self) {
+        self.notify_ext(0, NotifyAction::Increment);
+    }
+
+    /// Notifies the task, updating its notification value according to
+    /// `action`.
+    ///
+    /// The result is the notification value from before the update, or
+    /// [`None`] if the action was [`NotifyAction::NoOverwrite`] and the value
+    /// could not be written because a notification was already pending.
+    pub fn notify_ext(&self, value: u32, action: NotifyAction) -> Option<u32> {
+        let prev = with_notification(self, |n| {
+            let prev = n.value;
+            match action {
+                NotifyAction::None => {}
+                NotifyAction::SetBits => n.value |= value,
+                NotifyAction::Increment => n.value = n.value.wrapping_add(1),
+                NotifyAction::Overwrite => n.value = value,
+                NotifyAction::NoOverwrite if n.pending => return None,
+                NotifyAction::NoOverwrite => n.value = value,
+            }
+            n.pending = true;
+            Some(prev)
+        })?;
+        self.wake();
+        Some(prev)
+    }
+
+    #[inline]
+    /// Clears the pending notification state of the task, without modifying
+    /// its notification value. The result is `true` if a notification was
+    /// pending.
+    pub fn notify_clear(&self) -> bool {
+        with_notification(self, |n| core::mem::replace(&mut n.pending, false))
+    }
+
+    /// Waits for the notification value of the current task to become
+    /// non-zero, then either clears it (if `clear` is `true`) or decrements it.
+    ///
+    /// The result is the notification value from before it was cleared or
+    /// decremented, which is zero if the timeout elapsed first. A timeout of
+    /// [`None`] waits forever.
+    pub fn notify_take(clear: bool, timeout: Option<Duration>) -> u32 {
+        wait(NotifySelect::new(Receive::Take(clear)), timeout).unwrap_or(0)
+    }
+
+    /// Waits for a notification to the current task.
+    ///
+    /// If no notification is pending, the bits in `clear_on_entry` are
+    /// cleared from the notification value before waiting. Once a
+    /// notification has been received, the bits in `clear_on_exit` are
+    /// cleared. The result is the notification value from before the exit bits
+    /// were cleared, or [`None`] if the timeout elapsed first. A timeout of
+    /// [`None`] waits forever.
+    pub fn notify_wait(
+        clear_on_entry: u32,
+        clear_on_exit: u32,
+        timeout: Option<Duration>,
+    ) -> Option<u32> {
+        with_notification(&Task::current(), |n| {
+            if !n.pending {
+                n.value &= !clear_on_entry;
+            }
+        });
+        wait(NotifySelect::new(Receive::Wait(clear_on_exit)), timeout)
+    }
+
+    #[inline]
+    /// A [`Selectable`] event which occurs when the notification value of the
+    /// current task is non-zero. The value is reset to zero, and its previous
+    /// value is the result of the event.
+    pub fn notified() -> impl Selectable<u32> {
+        NotifySelect::new(Receive::Take(true))
+    }
+
+    #[inline]
+    /// A [`Selectable`] event which occurs when any of the bits in `mask` are
+    /// set in the notification value of the current task. Those bits are
+    /// cleared, and the result of the event is the notification value masked by
+    /// `mask`.
+    pub fn notified_bits(mask: u32) -> impl Selectable<u32> {
+        NotifySelect::new(Receive::Bits(mask))
+    }
+
+    #[inline]
+    /// Wakes the task if it is waiting in a select, without affecting its
+    /// notification value.
+    pub(crate) fn wake(&self) {
+        unsafe { bindings::task_notify(self.0) };
+    }
+}
+
+struct Notification {
+    task: Task,
+    value: u32,
+    pending: bool,
+}
+
+/// Describes how a waiting task receives its notification.
+#[derive(Clone, Copy)]
+enum Receive {
+    /// Wait for a non-zero value, then clear it (if `true`) or decrement it.
+    Take(bool),
+    /// Wait for any of the given bits, then clear them.
+    Bits(u32),
+    /// Wait for a pending notification, then clear the given bits.
+    Wait(u32),
+}
+
+impl Receive {
+    fn ready(self, n: &Notification) -> bool {
+        match self {
+            Receive::Take(_) => n.value != 0,
+            Receive::Bits(mask) => n.value & mask != 0,
+            Receive::Wait(_) => n.pending,
+        }
+    }
+
+    fn receive(self, n: &mut Notification) -> u32 {
+        let prev = n.value;
+        match self {
+            Receive::Take(true) => n.value = 0,
+            Receive::Take(false) => n.value -= 1,
+            Receive::Bits(mask) => {
+                n.value &= !mask;
+                return prev & mask;
+            }
+            Receive::Wait(clear_on_exit) => n.value &= !clear_on_exit,
+        }
+        n.pending = false;
+        prev
+    }
+}
+
+struct NotifySelect {
+    task: Task,
+    receive: Receive,
+}
+
+impl NotifySelect {
+    #[inline]
+    fn new(receive: Receive) -> Self {
+        Self {
+            task: Task::current(),
+            receive,
+        }
+    }
+}
+
+impl Selectable<u32> for NotifySelect {
+    fn poll(self) -> Result<u32, Self> {
+        let receive = self.receive;
+        with_notification(&self.task, |n| receive.ready(n).then(|| receive.receive(n))).ok_or(self)
+    }
+
+    fn sleep(&self) -> GenericSleep {
+        if with_notification(&self.task, |n| self.receive.ready(n)) {
+            GenericSleep::Timestamp(Instant::from_millis(0))
+        } else {
+            GenericSleep::NotifyTake(None)
+        }
+    }
+}
+
+#[inline]
+fn wait(event: NotifySelect, timeout: Option<Duration>) -> Option<u32> {
+    match timeout {
+        Some(timeout) => select(with_timeout(event, timeout)).ok(),
+        None => Some(select(event)),
+    }
+}
+
+/// Runs `f` on the notification state of `task`, discarding the state again
+/// if it is left empty.
+fn with_notification<U>(task: &Task, f: impl FnOnce(&mut Notification) -> U) -> U {
+    let mut lock = NOTIFICATIONS.call_once(|| Mutex::new(Vec::new())).lock();
+    let index = match lock.iter().position(|n| n.task == *task) {
+        Some(index) => index,
+        None => {
+            lock.push(Notification {
+                task: task.clone(),
+                value: 0,
+                pending: false,
+            });
+            lock.len() - 1
+        }
+    };
+    let r = f(&mut lock[index]);
+    if lock[index].value == 0 && !lock[index].pending {
+        lock.swap_remove(index);
+    }
+    r
+}
@@ -0,0 +1,154 @@
+use owner_monad::{Owner, OwnerMut};
+
+use super::{handle_event, Event, EventHandle, GenericSleep, Instant, Mutex, Selectable};
+use crate::error::Error;
+
+/// A primitive for notifying tasks of an occurrence, without any associated
+/// data.
+///
+/// If [`Notify::notify_one()`] is called while no task is waiting, a single
+/// permit is stored, which completes the next [`Notify::notified()`] event
+/// immediately; repeated calls do not store further permits.
+pub struct Notify(Mutex<NotifyData>);
+
+impl Notify {
+    #[inline]
+    /// Creates a new notify primitive. Panics on failure; see
+    /// [`Notify::try_new()`].
+    pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|err| panic!("failed to create notify: {:?}", err))
+    }
+
+    /// Creates a new notify primitive.
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self(Mutex::try_new(NotifyData {
+            waiters: 0,
+            wake_one: 0,
+            permit: false,
+            generation: 0,
+            event: Event::new(),
+        })?))
+    }
+
+    #[inline]
+    /// Wakes one waiting task, or stores a permit if no task is waiting.
+    pub fn notify_one(&self) {
+        self.notify_one_impl(true);
+    }
+
+    /// Wakes all waiting tasks. No permit is stored.
+    pub fn notify_all(&self) {
+        let mut lock = self.0.lock();
+        lock.generation = lock.generation.wrapping_add(1);
+        lock.wake_one = 0;
+        lock.event.notify();
+    }
+
+    /// A [`Selectable`] event which occurs when the task is notified.
+    ///
+    /// The task is counted as waiting from when the event is created until it
+    /// completes or is dropped.
+    pub fn notified(&'_ self) -> impl Selectable + '_ {
+        struct NotifySelect<'a> {
+            notify: &'a Notify,
+            generation: u32,
+            handle: EventHandle<&'a Mutex<NotifyData>>,
+        }
+
+        impl<'a> Selectable for NotifySelect<'a> {
+            fn poll(self) -> Result<(), Self> {
+                let generation = self.generation;
+                if self
+                    .handle
+                    .with(|mtx| mtx.lock().take(generation))
+                    .unwrap_or(false)
+                {
+                    Ok(())
+                } else {
+                    Err(self)
+                }
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if self.notify.0.lock().ready(self.generation) {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                } else {
+                    GenericSleep::NotifyTake(None)
+                }
+            }
+        }
+
+        impl<'a> Drop for NotifySelect<'a> {
+            fn drop(&mut self) {
+                let mut lock = self.notify.0.lock();
+                lock.waiters -= 1;
+                lock.wake_one = lock.wake_one.min(lock.waiters);
+            }
+        }
+
+        let generation = {
+            let mut lock = self.0.lock();
+            lock.waiters += 1;
+            lock.generation
+        };
+        NotifySelect {
+            notify: self,
+            generation,
+            handle: handle_event(&self.0),
+        }
+    }
+
+    pub(crate) fn notify_one_impl(&self, store_permit: bool) {
+        let mut lock = self.0.lock();
+        if lock.waiters > lock.wake_one {
+            lock.wake_one += 1;
+            lock.event.notify();
+        } else if lock.waiters == 0 && store_permit {
+            lock.permit = true;
+        }
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct NotifyData {
+    waiters: usize,
+    wake_one: usize,
+    permit: bool,
+    generation: u32,
+    event: Event,
+}
+
+impl NotifyData {
+    #[inline]
+    fn ready(&self, generation: u32) -> bool {
+        self.generation != generation || self.wake_one > 0 || self.permit
+    }
+
+    fn take(&mut self, generation: u32) -> bool {
+        if self.generation != generation {
+            true
+        } else if self.wake_one > 0 {
+            self.wake_one -= 1;
+            true
+        } else if self.permit {
+            self.permit = false;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl OwnerMut<Event> for &Mutex<NotifyData> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
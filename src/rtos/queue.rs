@@ -10,28 +10,105 @@ pub struct SendQueue<T>(Arc<dyn QueueShared<T> + Send + Sync>);
 
 impl<T> SendQueue<T> {
     #[inline]
-    /// Attempts to send an item on a queue.
+    /// Attempts to send an item on a queue. Fails if the queue is full or
+    /// closed.
     pub fn send(&self, item: T) -> bool {
         self.0.send(item)
     }
+
+    /// A [`Selectable`] event which resolves when `item` is sent on the queue,
+    /// waiting for capacity if the queue is full. If the queue is closed first,
+    /// the item is returned as an error.
+    ///
+    /// Since the underlying queue model drops items which it rejects, the item
+    /// is only handed over once the queue is known to have room: when it is
+    /// empty, or holds fewer items than it has held before.
+    pub fn select_send(&self, item: T) -> impl '_ + Selectable<Result<(), T>> {
+        struct SendSelect<'b, T> {
+            item: T,
+            data: &'b dyn QueueShared<T>,
+            handle: EventHandle<QueueWrapper<'b, T>>,
+        }
+
+        impl<'b, T> Selectable<Result<(), T>> for SendSelect<'b, T> {
+            fn poll(self) -> Result<Result<(), T>, Self> {
+                let Self { item, data, handle } = self;
+                match data.try_send(item) {
+                    Ok(()) => Ok(Ok(())),
+                    Err(item) if data.is_closed() => Ok(Err(item)),
+                    Err(item) => Err(Self { item, data, handle }),
+                }
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if self.data.has_room() || self.data.is_closed() {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                } else {
+                    GenericSleep::NotifyTake(None)
+                }
+            }
+        }
+
+        SendSelect {
+            item,
+            data: &*self.0,
+            handle: handle_event(QueueWrapper(&*self.0)),
+        }
+    }
+
+    #[inline]
+    /// Closes the queue. Further sends fail, and receivers observe the end of
+    /// the stream (see [`ReceiveQueue::select_next()`]) once the remaining
+    /// items have been received.
+    pub fn close(&self) {
+        self.0.close();
+    }
+
+    #[inline]
+    /// Returns `true` if the queue has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+    #[inline]
+    /// Gets the number of senders for the queue.
+    pub fn sender_count(&self) -> usize {
+        self.0.sender_count()
+    }
+
+    #[inline]
+    /// Gets the number of receivers for the queue.
+    pub fn receiver_count(&self) -> usize {
+        self.0.receiver_count()
+    }
 }
 
 impl<T> Clone for SendQueue<T> {
     fn clone(&self) -> Self {
+        self.0.add_sender(1);
         Self(self.0.clone())
     }
 }
 
+impl<T> Drop for SendQueue<T> {
+    fn drop(&mut self) {
+        self.0.add_sender(-1);
+    }
+}
+
 /// Represents the receive end of a message-passing queue.
 pub struct ReceiveQueue<T>(Arc<dyn QueueShared<T> + Send + Sync>);
 
 impl<T> ReceiveQueue<T> {
     /// A [`Selectable`] event which resolves when a value is received on the
     /// message-passing queue.
+    ///
+    /// This never resolves once the queue is closed and empty; see
+    /// [`ReceiveQueue::select_next()`].
     pub fn select(&self) -> impl '_ + Selectable<T> {
         struct ReceiveSelect<'b, T> {
             data: &'b dyn QueueShared<T>,
-            _handle: EventHandle<ReceiveWrapper<'b, T>>,
+            _handle: EventHandle<QueueWrapper<'b, T>>,
         }
 
         impl<'b, T> Selectable<T> for ReceiveSelect<'b, T> {
@@ -50,17 +127,75 @@ impl<T> ReceiveQueue<T> {
 
         ReceiveSelect {
             data: &*self.0,
-            _handle: handle_event(ReceiveWrapper(&*self.0)),
+            _handle: handle_event(QueueWrapper(&*self.0)),
         }
     }
+
+    /// A [`Selectable`] event which resolves when a value is received on the
+    /// message-passing queue, or with [`None`] when the queue is closed and
+    /// all remaining values have been received.
+    pub fn select_next(&self) -> impl '_ + Selectable<Option<T>> {
+        struct NextSelect<'b, T> {
+            data: &'b dyn QueueShared<T>,
+            _handle: EventHandle<QueueWrapper<'b, T>>,
+        }
+
+        impl<'b, T> Selectable<Option<T>> for NextSelect<'b, T> {
+            fn poll(self) -> Result<Option<T>, Self> {
+                match self.data.receive() {
+                    Some(item) => Ok(Some(item)),
+                    None if self.data.is_closed() => Ok(None),
+                    None => Err(self),
+                }
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if self.data.is_empty() && !self.data.is_closed() {
+                    GenericSleep::NotifyTake(None)
+                } else {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                }
+            }
+        }
+
+        NextSelect {
+            data: &*self.0,
+            _handle: handle_event(QueueWrapper(&*self.0)),
+        }
+    }
+
+    #[inline]
+    /// Returns `true` if the queue has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.0.is_closed()
+    }
+
+    #[inline]
+    /// Gets the number of senders for the queue.
+    pub fn sender_count(&self) -> usize {
+        self.0.sender_count()
+    }
+
+    #[inline]
+    /// Gets the number of receivers for the queue.
+    pub fn receiver_count(&self) -> usize {
+        self.0.receiver_count()
+    }
 }
 
 impl<T> Clone for ReceiveQueue<T> {
     fn clone(&self) -> Self {
+        self.0.add_receiver(1);
         Self(self.0.clone())
     }
 }
 
+impl<T> Drop for ReceiveQueue<T> {
+    fn drop(&mut self) {
+        self.0.add_receiver(-1);
+    }
+}
+
 /// The send/receive pair type returned by [`queue()`] and [`try_queue()`] for a
 /// given queue type.
 pub type QueuePair<Q> = (
@@ -83,23 +218,73 @@ pub fn try_queue<Q: 'static + QueueModel + Send + Sync>(queue: Q) -> Result<Queu
     impl<Q: QueueModel> QueueShared<Q::Item> for Queue<Q> {
         fn send(&self, item: Q::Item) -> bool {
             let mut lock = self.0.lock();
+            !lock.closed && lock.enqueue(item)
+        }
 
-            if lock.queue.enqueue(item) {
-                lock.event.notify();
-                true
-            } else {
-                false
+        fn try_send(&self, item: Q::Item) -> Result<(), Q::Item> {
+            let mut lock = self.0.lock();
+            if lock.closed || !lock.has_room() {
+                return Err(item);
             }
+            // The model only rejects an item here if it cannot hold any items
+            // at all, in which case nothing can ever be sent.
+            lock.enqueue(item);
+            Ok(())
         }
 
         fn receive(&self) -> Option<Q::Item> {
-            self.0.lock().queue.dequeue()
+            let mut lock = self.0.lock();
+            let item = lock.queue.dequeue()?;
+            lock.len = if lock.queue.is_empty() {
+                0
+            } else {
+                lock.len.saturating_sub(1)
+            };
+            // Wake any senders which are waiting for capacity.
+            lock.event.notify();
+            Some(item)
         }
 
         fn is_empty(&self) -> bool {
             self.0.lock().queue.is_empty()
         }
 
+        fn has_room(&self) -> bool {
+            self.0.lock().has_room()
+        }
+
+        fn close(&self) {
+            let mut lock = self.0.lock();
+            if !lock.closed {
+                lock.closed = true;
+                lock.event.notify();
+            }
+        }
+
+        fn is_closed(&self) -> bool {
+            self.0.lock().closed
+        }
+
+        fn sender_count(&self) -> usize {
+            self.0.lock().senders
+        }
+
+        fn receiver_count(&self) -> usize {
+            self.0.lock().receivers
+        }
+
+        fn add_sender(&self, delta: isize) -> usize {
+            let mut lock = self.0.lock();
+            lock.senders = lock.senders.wrapping_add_signed(delta);
+            lock.senders
+        }
+
+        fn add_receiver(&self, delta: isize) -> usize {
+            let mut lock = self.0.lock();
+            lock.receivers = lock.receivers.wrapping_add_signed(delta);
+            lock.receivers
+        }
+
         fn with_event<'a>(&'a self, f: &'a mut dyn FnMut(&mut Event)) {
             f(&mut self.0.lock().event);
         }
@@ -108,11 +293,41 @@ pub fn try_queue<Q: 'static + QueueModel + Send + Sync>(queue: Q) -> Result<Queu
     struct QueueData<Q: QueueModel> {
         event: Event,
         queue: Q,
+        /// The number of items in the queue, as far as can be told from the
+        /// items accepted and received.
+        len: usize,
+        /// The most items which the queue has held at once.
+        high_water: usize,
+        closed: bool,
+        senders: usize,
+        receivers: usize,
+    }
+
+    impl<Q: QueueModel> QueueData<Q> {
+        fn enqueue(&mut self, item: Q::Item) -> bool {
+            if !self.queue.enqueue(item) {
+                return false;
+            }
+            self.len += 1;
+            self.high_water = self.high_water.max(self.len);
+            self.event.notify();
+            true
+        }
+
+        #[inline]
+        fn has_room(&self) -> bool {
+            self.queue.is_empty() || self.len < self.high_water
+        }
     }
 
     let data = Arc::new(Queue(Mutex::try_new(QueueData {
         event: Event::new(),
         queue,
+        len: 0,
+        high_water: 0,
+        closed: false,
+        senders: 1,
+        receivers: 1,
     })?));
     let send = SendQueue(data.clone());
     let receive = ReceiveQueue(data);
@@ -122,13 +337,21 @@ pub fn try_queue<Q: 'static + QueueModel + Send + Sync>(queue: Q) -> Result<Queu
 trait QueueShared<T> {
     fn send(&self, item: T) -> bool;
     fn receive(&self) -> Option<T>;
+    fn try_send(&self, item: T) -> Result<(), T>;
     fn is_empty(&self) -> bool;
+    fn has_room(&self) -> bool;
+    fn close(&self);
+    fn is_closed(&self) -> bool;
+    fn sender_count(&self) -> usize;
+    fn receiver_count(&self) -> usize;
+    fn add_sender(&self, delta: isize) -> usize;
+    fn add_receiver(&self, delta: isize) -> usize;
     fn with_event<'a>(&'a self, f: &'a mut dyn FnMut(&mut Event));
 }
 
-struct ReceiveWrapper<'b, T>(&'b dyn QueueShared<T>);
+struct QueueWrapper<'b, T>(&'b dyn QueueShared<T>);
 
-impl<'b, T> OwnerMut<Event> for ReceiveWrapper<'b, T> {
+impl<'b, T> OwnerMut<Event> for QueueWrapper<'b, T> {
     fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
     where
         Event: 'a,
@@ -0,0 +1,309 @@
+use core::{
+    cell::UnsafeCell,
+    fmt::{self, Debug, Display, Formatter},
+    ops::{Deref, DerefMut},
+};
+use owner_monad::OwnerMut;
+
+use super::{handle_event, select, Event, EventHandle, GenericSleep, Instant, Mutex, Selectable};
+use crate::error::Error;
+
+/// Represents an object which can be accessed either by many readers at once
+/// or by a single writer.
+///
+/// # Behaviour
+///
+/// Writers are preferred: once a task is waiting to write, no new readers are
+/// admitted until it has acquired and released the lock. This prevents a steady
+/// stream of readers from starving writers, but means that a task which
+/// already holds a read lock must not try to take another one while a writer
+/// may be waiting, or it will deadlock.
+pub struct RwLock<T: ?Sized> {
+    state: Mutex<RwLockState>,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for RwLock<T> {}
+
+unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
+
+impl<T> RwLock<T> {
+    #[inline]
+    /// Creates a new read-write lock which wraps the given object. Panics on
+    /// failure; see [`RwLock::try_new()`].
+    pub fn new(data: T) -> Self {
+        Self::try_new(data).unwrap_or_else(|err| panic!("failed to create rwlock: {:?}", err))
+    }
+
+    /// Creates a new read-write lock which wraps the given object.
+    pub fn try_new(data: T) -> Result<Self, Error> {
+        Ok(Self {
+            state: Mutex::try_new(RwLockState {
+                readers: 0,
+                writer: false,
+                writers_waiting: 0,
+                event: Event::new(),
+            })?,
+            data: UnsafeCell::new(data),
+        })
+    }
+}
+
+impl<T: ?Sized> RwLock<T> {
+    #[inline]
+    /// Obtains shared read access to the object. Blocks until access can be
+    /// obtained.
+    pub fn read(&'_ self) -> RwLockReadGuard<'_, T> {
+        select(self.select_read())
+    }
+
+    #[inline]
+    /// Obtains exclusive write access to the object. Blocks until access can
+    /// be obtained.
+    pub fn write(&'_ self) -> RwLockWriteGuard<'_, T> {
+        select(self.select_write())
+    }
+
+    /// Obtains shared read access to the object, if it is available
+    /// immediately. Does not block.
+    pub fn try_read(&'_ self) -> Option<RwLockReadGuard<'_, T>> {
+        let mut state = self.state.lock();
+        if state.can_read() {
+            state.readers += 1;
+            Some(RwLockReadGuard(self))
+        } else {
+            None
+        }
+    }
+
+    /// Obtains exclusive write access to the object, if it is available
+    /// immediately. Does not block.
+    pub fn try_write(&'_ self) -> Option<RwLockWriteGuard<'_, T>> {
+        let mut state = self.state.lock();
+        if state.can_write() {
+            state.writer = true;
+            Some(RwLockWriteGuard(self))
+        } else {
+            None
+        }
+    }
+
+    /// A [`Selectable`] event which occurs when shared read access to the
+    /// object is obtained.
+    pub fn select_read(&'_ self) -> impl Selectable<RwLockReadGuard<'_, T>> + '_ {
+        struct ReadSelect<'a, T: ?Sized> {
+            lock: &'a RwLock<T>,
+            _handle: EventHandle<&'a Mutex<RwLockState>>,
+        }
+
+        impl<'a, T: ?Sized> Selectable<RwLockReadGuard<'a, T>> for ReadSelect<'a, T> {
+            fn poll(self) -> Result<RwLockReadGuard<'a, T>, Self> {
+                self.lock.try_read().ok_or(self)
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if self.lock.state.lock().can_read() {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                } else {
+                    GenericSleep::NotifyTake(None)
+                }
+            }
+        }
+
+        ReadSelect {
+            lock: self,
+            _handle: handle_event(&self.state),
+        }
+    }
+
+    /// A [`Selectable`] event which occurs when exclusive write access to the
+    /// object is obtained.
+    ///
+    /// For as long as the event exists, new readers are held off.
+    pub fn select_write(&'_ self) -> impl Selectable<RwLockWriteGuard<'_, T>> + '_ {
+        struct WriteSelect<'a, T: ?Sized> {
+            lock: &'a RwLock<T>,
+            waiting: bool,
+            _handle: EventHandle<&'a Mutex<RwLockState>>,
+        }
+
+        impl<'a, T: ?Sized> Selectable<RwLockWriteGuard<'a, T>> for WriteSelect<'a, T> {
+            fn poll(mut self) -> Result<RwLockWriteGuard<'a, T>, Self> {
+                let mut state = self.lock.state.lock();
+                if state.can_write() {
+                    state.writer = true;
+                    state.writers_waiting -= 1;
+                    drop(state);
+                    self.waiting = false;
+                    Ok(RwLockWriteGuard(self.lock))
+                } else {
+                    drop(state);
+                    Err(self)
+                }
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if self.lock.state.lock().can_write() {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                } else {
+                    GenericSleep::NotifyTake(None)
+                }
+            }
+        }
+
+        impl<'a, T: ?Sized> Drop for WriteSelect<'a, T> {
+            fn drop(&mut self) {
+                if self.waiting {
+                    let mut state = self.lock.state.lock();
+                    state.writers_waiting -= 1;
+                    // Readers may have been held off by this writer.
+                    state.event.notify();
+                }
+            }
+        }
+
+        self.state.lock().writers_waiting += 1;
+        WriteSelect {
+            lock: self,
+            waiting: true,
+            _handle: handle_event(&self.state),
+        }
+    }
+
+    #[inline]
+    /// Consumes the lock, returning the wrapped object.
+    pub fn into_inner(self) -> T
+    where
+        T: Sized,
+    {
+        self.data.into_inner()
+    }
+
+    #[inline]
+    /// Gets mutable access to the wrapped object. Since this requires a
+    /// mutable reference to the lock, no locking is needed.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+}
+
+impl<T: ?Sized + Default> Default for RwLock<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<T> From<T> for RwLock<T> {
+    #[inline]
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+/// Provides shared read access to an object controlled by an [`RwLock`] via
+/// the RAII pattern.
+pub struct RwLockReadGuard<'a, T: ?Sized>(&'a RwLock<T>);
+
+impl<T: ?Sized> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.0.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock();
+        state.readers -= 1;
+        if state.readers == 0 {
+            state.event.notify();
+        }
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for RwLockReadGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for RwLockReadGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// Provides exclusive write access to an object controlled by an [`RwLock`]
+/// via the RAII pattern.
+pub struct RwLockWriteGuard<'a, T: ?Sized>(&'a RwLock<T>);
+
+impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.0.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.0.data.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.0.state.lock();
+        state.writer = false;
+        state.event.notify();
+    }
+}
+
+impl<T: ?Sized + Debug> Debug for RwLockWriteGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + Display> Display for RwLockWriteGuard<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+struct RwLockState {
+    readers: usize,
+    writer: bool,
+    writers_waiting: usize,
+    event: Event,
+}
+
+impl RwLockState {
+    #[inline]
+    fn can_read(&self) -> bool {
+        !self.writer && self.writers_waiting == 0
+    }
+
+    #[inline]
+    fn can_write(&self) -> bool {
+        !self.writer && self.readers == 0
+    }
+}
+
+impl OwnerMut<Event> for &Mutex<RwLockState> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
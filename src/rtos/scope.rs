@@ -0,0 +1,144 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{marker::PhantomData, mem::transmute};
+
+use super::{select, select_map, Mutex, Promise, Selectable, Task};
+use crate::error::Error;
+
+/// Creates a scope for spawning tasks which may borrow non-`'static` data.
+///
+/// The function `f` is given a [`Scope`], through which tasks can be spawned.
+/// Once `f` returns, `scope` waits for every task spawned in the scope
+/// (including tasks spawned by those tasks) to finish before returning, so
+/// borrows of local data cannot outlive the data itself.
+///
+/// # Example
+/// ```
+/// let mut counts = [0u32; 2];
+/// let total = 10;
+/// scope(|s| {
+///     for count in counts.iter_mut() {
+///         s.spawn(move || {
+///             for _ in 0..total {
+///                 *count += 1;
+///             }
+///         })
+///         .unwrap();
+///     }
+/// });
+/// println!("counts = {:?}", counts);
+/// ```
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    let scope = Scope {
+        tasks: Mutex::new(Vec::new()),
+        scope: PhantomData,
+        env: PhantomData,
+    };
+    let r = f(&scope);
+    // Tasks may spawn further tasks into the scope, but only before they
+    // finish, so waiting on each task in turn eventually drains the list.
+    loop {
+        let promise = scope.tasks.lock().pop();
+        match promise {
+            Some(promise) => select(promise.done()),
+            None => return r,
+        };
+    }
+}
+
+/// A scope for spawning tasks which may borrow non-`'static` data; see
+/// [`scope()`].
+pub struct Scope<'scope, 'env: 'scope> {
+    tasks: Mutex<Vec<Promise>>,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    #[inline]
+    /// Spawns a new task within the scope, with no name and the default
+    /// priority and stack depth.
+    pub fn spawn<T, F>(&'scope self, f: F) -> Result<ScopedJoinHandle<'scope, T>, Error>
+    where
+        T: Send + 'scope,
+        F: FnOnce() -> T + Send + 'scope,
+    {
+        self.spawn_ext("", Task::DEFAULT_PRIORITY, Task::DEFAULT_STACK_DEPTH, f)
+    }
+
+    /// Spawns a new task within the scope, with the specified name, priority
+    /// and stack depth.
+    pub fn spawn_ext<T, F>(
+        &'scope self,
+        name: &str,
+        priority: u32,
+        stack_depth: u16,
+        f: F,
+    ) -> Result<ScopedJoinHandle<'scope, T>, Error>
+    where
+        T: Send + 'scope,
+        F: FnOnce() -> T + Send + 'scope,
+    {
+        let result = Arc::new(Mutex::try_new(None)?);
+        let task_result = result.clone();
+        let run: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            let r = f();
+            *task_result.lock() = Some(r);
+        });
+        // This is safe because the scope does not end until the promise below
+        // is resolved, which happens only after `run` (along with everything
+        // it borrows) has been consumed.
+        let run: Box<dyn FnOnce() + Send + 'static> = unsafe { transmute(run) };
+        let (promise, resolve) = Promise::new();
+        let task = Task::spawn_ext(name, priority, stack_depth, move || {
+            run();
+            resolve(());
+        })?;
+        self.tasks.lock().push(promise.clone());
+        Ok(ScopedJoinHandle {
+            task,
+            promise,
+            result,
+            _scope: PhantomData,
+        })
+    }
+}
+
+/// An owned handle to a task spawned within a [`Scope`].
+pub struct ScopedJoinHandle<'scope, T> {
+    task: Task,
+    promise: Promise,
+    result: Arc<Mutex<Option<T>>>,
+    _scope: PhantomData<&'scope ()>,
+}
+
+impl<'scope, T> ScopedJoinHandle<'scope, T> {
+    #[inline]
+    /// Gets the task which is running.
+    pub fn task(&self) -> &Task {
+        &self.task
+    }
+
+    #[inline]
+    /// A [`Selectable`] event which occurs when the task finishes.
+    pub fn done(&'_ self) -> impl Selectable + '_ {
+        select_map(self.promise.done(), |_| ())
+    }
+
+    #[inline]
+    /// Returns `true` if the task has finished.
+    pub fn is_finished(&self) -> bool {
+        self.done().poll().is_ok()
+    }
+
+    /// Waits for the task to finish and returns its result.
+    pub fn join(self) -> T {
+        select(self.done());
+        self.result
+            .lock()
+            .take()
+            .expect("scoped task finished without a result")
+    }
+}
@@ -1,12 +1,17 @@
-use core::{convert::TryInto, time::Duration};
+use core::{convert::TryInto, mem::forget, time::Duration};
+use owner_monad::OwnerMut;
 
+use super::{handle_event, select, Event, EventHandle, GenericSleep, Instant, Mutex, Selectable};
 use crate::{
     bindings,
     error::{from_errno, Error, SentinelError},
 };
 
-/// Represents a FreeRTOS counting semaphore.
-pub struct Semaphore(bindings::sem_t);
+/// Represents a FreeRTOS counting or binary semaphore.
+pub struct Semaphore {
+    sem: bindings::sem_t,
+    data: Mutex<SemaphoreData>,
+}
 
 impl Semaphore {
     #[inline]
@@ -18,9 +23,36 @@ impl Semaphore {
 
     /// Creates a new semaphore.
     pub fn try_new(max_count: u32, init_count: u32) -> Result<Self, Error> {
-        Ok(Self(
-            unsafe { bindings::sem_create(max_count, init_count) }.check()?,
-        ))
+        Self::from_raw(unsafe { bindings::sem_create(max_count, init_count) })
+    }
+
+    #[inline]
+    /// Creates a new binary semaphore (i.e., one with a maximum count of 1),
+    /// which is initially empty. Panics on failure; see
+    /// [`Semaphore::try_binary`].
+    pub fn binary() -> Self {
+        Self::try_binary().unwrap_or_else(|err| panic!("failed to create semaphore: {}", err))
+    }
+
+    #[inline]
+    /// Creates a new binary semaphore (i.e., one with a maximum count of 1),
+    /// which is initially empty.
+    pub fn try_binary() -> Result<Self, Error> {
+        Self::from_raw(unsafe { bindings::sem_binary_create() })
+    }
+
+    fn from_raw(sem: bindings::sem_t) -> Result<Self, Error> {
+        let sem = sem.check()?;
+        let data = Mutex::try_new(SemaphoreData {
+            event: Event::new(),
+        });
+        match data {
+            Ok(data) => Ok(Self { sem, data }),
+            Err(err) => {
+                unsafe { bindings::sem_delete(sem) };
+                Err(err)
+            }
+        }
     }
 
     #[inline]
@@ -28,7 +60,7 @@ impl Semaphore {
     /// (i.e., its count decremented). If the semaphore cannot be taken (due
     /// to timeout or other reason), an error is returned.
     pub fn wait(&self, timeout: Duration) -> Result<(), Error> {
-        if unsafe { bindings::sem_wait(self.0, timeout.as_millis().try_into()?) } {
+        if unsafe { bindings::sem_wait(self.sem, timeout.as_millis().try_into()?) } {
             Ok(())
         } else {
             Err(from_errno())
@@ -39,7 +71,8 @@ impl Semaphore {
     /// Increments the semaphore's count. If the semaphore cannot be given, an
     /// error is returned.
     pub fn post(&self) -> Result<(), Error> {
-        if unsafe { bindings::sem_post(self.0) } {
+        if unsafe { bindings::sem_post(self.sem) } {
+            self.data.lock().event.notify();
             Ok(())
         } else {
             Err(from_errno())
@@ -49,16 +82,108 @@ impl Semaphore {
     #[inline]
     /// Gets the semaphore's current count.
     pub fn count(&self) -> u32 {
-        unsafe { bindings::sem_get_count(self.0) }
+        unsafe { bindings::sem_get_count(self.sem) }
+    }
+
+    #[inline]
+    /// Takes an instance of the semaphore, returning a [`SemaphorePermit`]
+    /// which gives it back when dropped. Blocks until an instance can be
+    /// taken.
+    pub fn acquire(&'_ self) -> SemaphorePermit<'_> {
+        select(self.select_acquire())
+    }
+
+    #[inline]
+    /// Takes an instance of the semaphore, returning a [`SemaphorePermit`]
+    /// which gives it back when dropped, if one is available immediately. Does
+    /// not block.
+    pub fn try_acquire(&'_ self) -> Option<SemaphorePermit<'_>> {
+        if unsafe { bindings::sem_wait(self.sem, 0) } {
+            Some(SemaphorePermit(self))
+        } else {
+            None
+        }
+    }
+
+    /// A [`Selectable`] event which occurs when an instance of the semaphore
+    /// is taken, with a [`SemaphorePermit`] which gives it back when dropped.
+    ///
+    /// Only posts made via [`Semaphore::post()`] (including by dropping a
+    /// permit) wake the event.
+    pub fn select_acquire(&'_ self) -> impl Selectable<SemaphorePermit<'_>> + '_ {
+        struct AcquireSelect<'a> {
+            sem: &'a Semaphore,
+            _handle: EventHandle<&'a Mutex<SemaphoreData>>,
+        }
+
+        impl<'a> Selectable<SemaphorePermit<'a>> for AcquireSelect<'a> {
+            #[inline]
+            fn poll(self) -> Result<SemaphorePermit<'a>, Self> {
+                self.sem.try_acquire().ok_or(self)
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if self.sem.count() > 0 {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                } else {
+                    GenericSleep::NotifyTake(None)
+                }
+            }
+        }
+
+        AcquireSelect {
+            sem: self,
+            _handle: handle_event(&self.data),
+        }
     }
 }
 
 impl Drop for Semaphore {
     fn drop(&mut self) {
-        unsafe { bindings::sem_delete(self.0) }
+        unsafe { bindings::sem_delete(self.sem) }
     }
 }
 
 unsafe impl Send for Semaphore {}
 
 unsafe impl Sync for Semaphore {}
+
+/// Represents an instance taken from a [`Semaphore`], which is given back
+/// when the permit is dropped.
+pub struct SemaphorePermit<'a>(&'a Semaphore);
+
+impl<'a> SemaphorePermit<'a> {
+    #[inline]
+    /// Gets the semaphore which the permit was taken from.
+    pub fn semaphore(&self) -> &'a Semaphore {
+        self.0
+    }
+
+    #[inline]
+    /// Consumes the permit without giving the instance back to the semaphore.
+    pub fn forget(self) {
+        forget(self);
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        // This can only fail if the semaphore was posted beyond its maximum
+        // count while the permit was held, in which case the instance is
+        // already accounted for.
+        self.0.post().ok();
+    }
+}
+
+struct SemaphoreData {
+    event: Event,
+}
+
+impl OwnerMut<Event> for &Mutex<SemaphoreData> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
@@ -0,0 +1,338 @@
+use alloc::{
+    boxed::Box,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{cmp::min, time::Duration};
+use owner_monad::{Owner, OwnerMut};
+use spin::once::Once;
+
+use super::{
+    delay_until, handle_event, select_option, time_since_start, Event, EventHandle, GenericSleep,
+    Instant, Mutex, Selectable, Task,
+};
+use crate::{error::Error, select};
+
+static SERVICE: Once<Mutex<ServiceData>> = Once::new();
+
+/// The shortest period of a timer; a periodic timer with a zero period would
+/// keep the timer task busy forever.
+const MIN_PERIOD: Duration = Duration::from_millis(1);
+
+/// A software timer, which fires once or periodically after a given period.
+///
+/// All timers are driven by a single shared timer task, which is spawned when
+/// the first timer is created. When a timer fires, its callback (if any) is run
+/// on that task, so callbacks should be short and must not block; longer jobs
+/// should instead wait on [`Timer::fired()`] from their own task.
+///
+/// Timers are created in the stopped state; see [`Timer::start()`]. A timer is
+/// stopped when it is dropped.
+///
+/// Periods shorter than one millisecond are rounded up to one millisecond.
+pub struct Timer(Arc<TimerShared>);
+
+impl Timer {
+    #[inline]
+    /// Creates a new timer which fires once, `period` after it is started.
+    pub fn one_shot(period: Duration) -> Result<Self, Error> {
+        Self::new_internal(period, false, None)
+    }
+
+    #[inline]
+    /// Creates a new timer which fires every `period` once it is started.
+    pub fn periodic(period: Duration) -> Result<Self, Error> {
+        Self::new_internal(period, true, None)
+    }
+
+    #[inline]
+    /// Creates a new timer which fires once, `period` after it is started,
+    /// running `callback` on the timer task.
+    pub fn one_shot_with(
+        period: Duration,
+        callback: impl FnMut() + Send + 'static,
+    ) -> Result<Self, Error> {
+        Self::new_internal(period, false, Some(Box::new(callback)))
+    }
+
+    #[inline]
+    /// Creates a new timer which fires every `period` once it is started,
+    /// running `callback` on the timer task.
+    pub fn periodic_with(
+        period: Duration,
+        callback: impl FnMut() + Send + 'static,
+    ) -> Result<Self, Error> {
+        Self::new_internal(period, true, Some(Box::new(callback)))
+    }
+
+    fn new_internal(
+        period: Duration,
+        periodic: bool,
+        callback: Option<Box<dyn FnMut() + Send>>,
+    ) -> Result<Self, Error> {
+        let timer = Arc::new(TimerShared {
+            state: Mutex::try_new(TimerState {
+                period: period.max(MIN_PERIOD),
+                periodic,
+                next: None,
+                fires: 0,
+                event: Event::new(),
+            })?,
+            callback: callback.map(Mutex::try_new).transpose()?,
+        });
+        let mut service = service().lock();
+        service.timers.push(Arc::downgrade(&timer));
+        service.wake();
+        Ok(Self(timer))
+    }
+
+    #[inline]
+    /// Starts the timer, if it is not already running; it will fire one
+    /// period from now.
+    pub fn start(&self) {
+        self.update(|state| {
+            if state.next.is_none() {
+                state.next = Some(time_since_start() + state.period);
+            }
+        });
+    }
+
+    #[inline]
+    /// Stops the timer, if it is running.
+    pub fn stop(&self) {
+        self.update(|state| state.next = None);
+    }
+
+    #[inline]
+    /// Restarts the timer, so that it will fire one period from now, whether
+    /// or not it is already running.
+    pub fn reset(&self) {
+        self.update(|state| state.next = Some(time_since_start() + state.period));
+    }
+
+    /// Changes the period of the timer. If the timer is running, it is
+    /// restarted with the new period.
+    pub fn set_period(&self, period: Duration) {
+        let period = period.max(MIN_PERIOD);
+        self.update(|state| {
+            state.period = period;
+            if state.next.is_some() {
+                state.next = Some(time_since_start() + period);
+            }
+        });
+    }
+
+    #[inline]
+    /// Gets the period of the timer.
+    pub fn period(&self) -> Duration {
+        self.0.state.lock().period
+    }
+
+    #[inline]
+    /// Returns `true` if the timer is running.
+    pub fn is_active(&self) -> bool {
+        self.0.state.lock().next.is_some()
+    }
+
+    #[inline]
+    /// Gets the number of times the timer has fired since it was created.
+    pub fn fire_count(&self) -> u32 {
+        self.0.state.lock().fires
+    }
+
+    /// A [`Selectable`] event which occurs when the timer next fires.
+    pub fn fired(&'_ self) -> impl Selectable + '_ {
+        struct TimerSelect<'a> {
+            fires: u32,
+            handle: EventHandle<&'a Mutex<TimerState>>,
+        }
+
+        impl<'a> Selectable for TimerSelect<'a> {
+            fn poll(self) -> Result<(), Self> {
+                if self
+                    .handle
+                    .with(|mtx| mtx.lock().fires != self.fires)
+                    .unwrap_or(false)
+                {
+                    Ok(())
+                } else {
+                    Err(self)
+                }
+            }
+
+            #[inline]
+            fn sleep(&self) -> GenericSleep {
+                GenericSleep::NotifyTake(None)
+            }
+        }
+
+        TimerSelect {
+            fires: self.fire_count(),
+            handle: handle_event(&self.0.state),
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut TimerState)) {
+        f(&mut self.0.state.lock());
+        service().lock().wake();
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+struct TimerShared {
+    state: Mutex<TimerState>,
+    callback: Option<Mutex<Box<dyn FnMut() + Send>>>,
+}
+
+struct TimerState {
+    period: Duration,
+    periodic: bool,
+    next: Option<Instant>,
+    fires: u32,
+    event: Event,
+}
+
+impl TimerState {
+    fn fire(&mut self, now: Instant) {
+        self.fires = self.fires.wrapping_add(1);
+        self.event.notify();
+        self.next = match self.next {
+            Some(next) if self.periodic => {
+                // Periods which were missed entirely are skipped, rather than
+                // fired in a burst.
+                let next = next + self.period;
+                Some(if next > now { next } else { now + self.period })
+            }
+            _ => None,
+        };
+    }
+}
+
+impl OwnerMut<Event> for &Mutex<TimerState> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
+
+struct ServiceData {
+    timers: Vec<Weak<TimerShared>>,
+    generation: u32,
+    event: Event,
+}
+
+impl ServiceData {
+    #[inline]
+    fn wake(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        self.event.notify();
+    }
+}
+
+impl OwnerMut<Event> for &Mutex<ServiceData> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
+
+fn service() -> &'static Mutex<ServiceData> {
+    let mut created = false;
+    let service = SERVICE.call_once(|| {
+        created = true;
+        Mutex::new(ServiceData {
+            timers: Vec::new(),
+            generation: 0,
+            event: Event::new(),
+        })
+    });
+    if created {
+        Task::spawn_ext(
+            "timer-service",
+            Task::DEFAULT_PRIORITY,
+            Task::DEFAULT_STACK_DEPTH,
+            move || run_service(service),
+        )
+        .unwrap_or_else(|err| panic!("failed to start timer service: {:?}", err));
+    }
+    service
+}
+
+fn run_service(service: &'static Mutex<ServiceData>) {
+    loop {
+        let now = time_since_start();
+        let mut due = Vec::new();
+        let mut next: Option<Instant> = None;
+        let generation = {
+            let mut lock = service.lock();
+            lock.timers.retain(|t| t.strong_count() > 0);
+            for timer in lock.timers.iter().filter_map(Weak::upgrade) {
+                let mut state = timer.state.lock();
+                if state.next.map_or(false, |t| t <= now) {
+                    state.fire(now);
+                    drop(state);
+                    due.push(timer);
+                } else if let Some(t) = state.next {
+                    next = Some(next.map_or(t, |n| min(n, t)));
+                }
+            }
+            lock.generation
+        };
+
+        for timer in due.iter() {
+            if let Some(callback) = &timer.callback {
+                (callback.lock())();
+            }
+        }
+        // Timers which fired may still be running, so go around again to
+        // compute their next deadlines.
+        if !due.is_empty() {
+            continue;
+        }
+
+        select! {
+            _ = service_changed(service, generation) => {},
+            _ = select_option(next.map(delay_until)) => {},
+        }
+    }
+}
+
+fn service_changed(service: &'static Mutex<ServiceData>, generation: u32) -> impl Selectable {
+    struct ServiceSelect {
+        generation: u32,
+        handle: EventHandle<&'static Mutex<ServiceData>>,
+    }
+
+    impl Selectable for ServiceSelect {
+        fn poll(self) -> Result<(), Self> {
+            if self
+                .handle
+                .with(|mtx| mtx.lock().generation != self.generation)
+                .unwrap_or(false)
+            {
+                Ok(())
+            } else {
+                Err(self)
+            }
+        }
+
+        #[inline]
+        fn sleep(&self) -> GenericSleep {
+            GenericSleep::NotifyTake(None)
+        }
+    }
+
+    ServiceSelect {
+        generation,
+        handle: handle_event(service),
+    }
+}
@@ -0,0 +1,109 @@
+use alloc::sync::Arc;
+use owner_monad::OwnerMut;
+
+use super::{handle_event, Event, EventHandle, GenericSleep, Instant, Mutex, Selectable};
+use crate::error::Error;
+
+#[derive(Clone)]
+/// Waits for a group of operations to finish. Inspired by `sync.WaitGroup` in
+/// the Go programming language.
+///
+/// The group holds a counter of outstanding operations, which is increased via
+/// [`WaitGroup::add()`] before each operation starts and decreased via
+/// [`WaitGroup::done()`] when it finishes. [`WaitGroup::wait()`] occurs when
+/// the counter reaches zero. Copies of a wait group share the same counter.
+pub struct WaitGroup(Arc<Mutex<WaitGroupData>>);
+
+impl WaitGroup {
+    #[inline]
+    /// Creates a new wait group with a counter of zero. Panics on failure; see
+    /// [`WaitGroup::try_new()`].
+    pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|err| panic!("failed to create wait group: {:?}", err))
+    }
+
+    /// Creates a new wait group with a counter of zero.
+    pub fn try_new() -> Result<Self, Error> {
+        Ok(Self(Arc::new(Mutex::try_new(WaitGroupData {
+            count: 0,
+            event: Event::new(),
+        })?)))
+    }
+
+    #[inline]
+    /// Adds `n` outstanding operations to the group.
+    pub fn add(&self, n: usize) {
+        self.0.lock().count += n;
+    }
+
+    /// Marks one outstanding operation as finished. Panics if there are no
+    /// outstanding operations.
+    pub fn done(&self) {
+        let mut lock = self.0.lock();
+        lock.count = lock
+            .count
+            .checked_sub(1)
+            .expect("WaitGroup::done called with no outstanding operations");
+        if lock.count == 0 {
+            lock.event.notify();
+        }
+    }
+
+    #[inline]
+    /// Gets the number of outstanding operations.
+    pub fn count(&self) -> usize {
+        self.0.lock().count
+    }
+
+    /// A [`Selectable`] event which occurs when there are no outstanding
+    /// operations.
+    pub fn wait(&'_ self) -> impl Selectable + '_ {
+        struct WaitGroupSelect<'a> {
+            group: &'a WaitGroup,
+            _handle: EventHandle<&'a Mutex<WaitGroupData>>,
+        }
+
+        impl<'a> Selectable for WaitGroupSelect<'a> {
+            fn poll(self) -> Result<(), Self> {
+                if self.group.count() == 0 {
+                    Ok(())
+                } else {
+                    Err(self)
+                }
+            }
+
+            fn sleep(&self) -> GenericSleep {
+                if self.group.count() == 0 {
+                    GenericSleep::Timestamp(Instant::from_millis(0))
+                } else {
+                    GenericSleep::NotifyTake(None)
+                }
+            }
+        }
+
+        WaitGroupSelect {
+            group: self,
+            _handle: handle_event(&*self.0),
+        }
+    }
+}
+
+impl Default for WaitGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct WaitGroupData {
+    count: usize,
+    event: Event,
+}
+
+impl OwnerMut<Event> for &Mutex<WaitGroupData> {
+    fn with<'a, U>(&'a mut self, f: impl FnOnce(&mut Event) -> U) -> Option<U>
+    where
+        Event: 'a,
+    {
+        Some(f(&mut self.try_lock().ok()?.event))
+    }
+}
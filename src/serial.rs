@@ -5,11 +5,19 @@ use core::convert::TryInto;
 use crate::{
     bindings,
     error::{Error, SentinelError},
-    smart_port::{smart_port_type, DeviceType},
+    smart_port::{smart_port_type, DeviceType, PortMonitor},
 };
 
 /// Represents the generic serial interface of a smart port.
-pub struct Serial(u8);
+///
+/// The port remembers its baudrate, and is re-enabled with it the next time one
+/// of its methods is called after the device is unplugged and plugged back in;
+/// see [`Serial::check_connection()`].
+pub struct Serial {
+    port: u8,
+    baudrate: i32,
+    monitor: PortMonitor,
+}
 
 impl Serial {
     /// Constructs a new generic serial port. Panics on failure; see
@@ -38,13 +46,19 @@ impl Serial {
         bindings::serial_enable(port).check()?;
         while smart_port_type(port) != DeviceType::Serial {}
         bindings::serial_set_baudrate(port, baudrate).check()?;
-        Ok(Self(port))
+        Ok(Self {
+            port,
+            baudrate,
+            monitor: PortMonitor::new(port, DeviceType::Serial),
+        })
     }
 
     #[inline]
     /// Changes the baudrate of the serial port.
     pub fn set_baudrate(&mut self, baudrate: i32) -> Result<(), Error> {
-        unsafe { bindings::serial_set_baudrate(self.0, baudrate) }.check()?;
+        self.check_connection()?;
+        self.baudrate = baudrate;
+        unsafe { bindings::serial_set_baudrate(self.port, baudrate) }.check()?;
         Ok(())
     }
 
@@ -52,7 +66,8 @@ impl Serial {
     /// Gets the number of bytes available to read in the input buffer of the
     /// serial port.
     pub fn get_read_avail(&self) -> Result<usize, Error> {
-        Ok(unsafe { bindings::serial_get_read_avail(self.0) }
+        self.check_connection()?;
+        Ok(unsafe { bindings::serial_get_read_avail(self.port) }
             .check()?
             .try_into()?)
     }
@@ -60,7 +75,8 @@ impl Serial {
     #[inline]
     /// Gets the number of bytes free in the output buffer of the serial port.
     pub fn get_write_free(&self) -> Result<usize, Error> {
-        Ok(unsafe { bindings::serial_get_write_free(self.0) }
+        self.check_connection()?;
+        Ok(unsafe { bindings::serial_get_write_free(self.port) }
             .check()?
             .try_into()?)
     }
@@ -69,7 +85,8 @@ impl Serial {
     /// Reads the next available byte in the input buffer of the serial port
     /// without removing it.
     pub fn peek_byte(&self) -> Result<u8, Error> {
-        Ok(unsafe { bindings::serial_peek_byte(self.0) }
+        self.check_connection()?;
+        Ok(unsafe { bindings::serial_peek_byte(self.port) }
             .check()?
             .try_into()?)
     }
@@ -77,7 +94,8 @@ impl Serial {
     #[inline]
     /// Reads the next available byte in the input buffer of the serial port.
     pub fn read_byte(&mut self) -> Result<u8, Error> {
-        Ok(unsafe { bindings::serial_read_byte(self.0) }
+        self.check_connection()?;
+        Ok(unsafe { bindings::serial_read_byte(self.port) }
             .check()?
             .try_into()?)
     }
@@ -85,7 +103,8 @@ impl Serial {
     #[inline]
     /// Writes the given byte to the output buffer of the serial port.
     pub fn write_byte(&mut self, byte: u8) -> Result<(), Error> {
-        unsafe { bindings::serial_write_byte(self.0, byte) }.check()?;
+        self.check_connection()?;
+        unsafe { bindings::serial_write_byte(self.port, byte) }.check()?;
         Ok(())
     }
 
@@ -93,19 +112,25 @@ impl Serial {
     /// Reads as many bytes as possible from the input buffer of the serial port
     /// into the given buffer, returning the number read.
     pub fn read(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
-        Ok(
-            unsafe { bindings::serial_read(self.0, buffer.as_mut_ptr(), buffer.len().try_into()?) }
-                .check()?
-                .try_into()?,
-        )
+        self.check_connection()?;
+        Ok(unsafe {
+            bindings::serial_read(self.port, buffer.as_mut_ptr(), buffer.len().try_into()?)
+        }
+        .check()?
+        .try_into()?)
     }
 
     #[inline]
     /// Writes as many bytes as possible to the output buffer of the serial port
     /// from the given buffer, returning the number written.
     pub fn write(&mut self, buffer: &[u8]) -> Result<usize, Error> {
+        self.check_connection()?;
         Ok(unsafe {
-            bindings::serial_write(self.0, buffer.as_ptr() as *mut _, buffer.len().try_into()?)
+            bindings::serial_write(
+                self.port,
+                buffer.as_ptr() as *mut _,
+                buffer.len().try_into()?,
+            )
         }
         .check()?
         .try_into()?)
@@ -115,7 +140,45 @@ impl Serial {
     /// Clears the internal input and output buffers of the serial port,
     /// effectively resetting its state.
     pub fn flush(&mut self) -> Result<(), Error> {
-        unsafe { bindings::serial_flush(self.0) }.check()?;
+        self.check_connection()?;
+        unsafe { bindings::serial_flush(self.port) }.check()?;
         Ok(())
     }
+
+    /// Checks whether the port has left generic serial mode, e.g. because the
+    /// device was unplugged and plugged back in, re-enabling the serial
+    /// interface and re-applying its baudrate if so. Returns `true` if the
+    /// port was re-enabled.
+    ///
+    /// A replugged port does not report a serial device until it is enabled
+    /// again, so this re-enables the port as soon as it reports anything else,
+    /// and keeps retrying until that succeeds.
+    ///
+    /// This is called automatically by every method which talks to the port.
+    pub fn check_connection(&self) -> Result<bool, Error> {
+        self.monitor.poll();
+        if self.monitor.is_configured() {
+            return Ok(false);
+        }
+        unsafe {
+            bindings::serial_enable(self.port).check()?;
+            bindings::serial_set_baudrate(self.port, self.baudrate).check()?;
+        }
+        self.monitor.mark_configured();
+        Ok(true)
+    }
+
+    #[inline]
+    /// Checks whether the port was in generic serial mode as of the last call
+    /// to [`Serial::check_connection()`].
+    pub fn is_connected(&self) -> bool {
+        self.monitor.is_connected()
+    }
+
+    #[inline]
+    /// Gets the number of times the port has returned to generic serial mode
+    /// after leaving it, since the port was constructed.
+    pub fn reconnect_count(&self) -> u32 {
+        self.monitor.reconnects()
+    }
 }
@@ -9,7 +9,10 @@ use crate::{
     motor::{EncoderUnits, Gearset, Motor},
     serial::Serial,
 };
-use core::convert::{TryFrom, TryInto};
+use core::{
+    convert::{TryFrom, TryInto},
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+};
 
 /// A struct which represents an unconfigured smart port.
 pub struct SmartPort {
@@ -150,3 +153,72 @@ impl From<bindings::v5_device_e_t> for DeviceType {
 pub unsafe fn smart_port_type(port: u8) -> DeviceType {
     bindings::registry_get_plugged_type(port - 1).into()
 }
+
+/// Tracks whether the device on a smart port has been unplugged and plugged
+/// back in, so that its owner can re-apply any configuration which the
+/// firmware may have lost in the meantime.
+///
+/// The state is kept in atomics so that the port can be sampled from methods
+/// taking `&self`. A reconnected device is reported by every sample until its
+/// owner calls [`PortMonitor::mark_configured()`], so a configuration which
+/// fails to apply is retried.
+pub(crate) struct PortMonitor {
+    port: u8,
+    device_type: DeviceType,
+    connected: AtomicBool,
+    configured: AtomicBool,
+    reconnects: AtomicU32,
+}
+
+impl PortMonitor {
+    /// Creates a new monitor for a device of the given type on a smart port,
+    /// which is assumed to have been configured already.
+    pub(crate) fn new(port: u8, device_type: DeviceType) -> Self {
+        Self {
+            port,
+            device_type,
+            connected: AtomicBool::new(unsafe { smart_port_type(port) } == device_type),
+            configured: AtomicBool::new(true),
+            reconnects: AtomicU32::new(0),
+        }
+    }
+
+    /// Samples the port, returning `true` if the device is connected but has
+    /// not been configured since it was plugged back in.
+    pub(crate) fn poll(&self) -> bool {
+        let connected = unsafe { smart_port_type(self.port) } == self.device_type;
+        if !connected {
+            self.configured.store(false, Ordering::Release);
+        }
+        let was_connected = self.connected.swap(connected, Ordering::AcqRel);
+        if connected && !was_connected {
+            self.reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+        connected && !self.is_configured()
+    }
+
+    #[inline]
+    /// Records that the device's configuration has been re-applied.
+    pub(crate) fn mark_configured(&self) {
+        self.configured.store(true, Ordering::Release);
+    }
+
+    #[inline]
+    /// Whether the device has been configured since it was last seen to be
+    /// unplugged.
+    pub(crate) fn is_configured(&self) -> bool {
+        self.configured.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    /// Whether the device was connected as of the last sample.
+    pub(crate) fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    /// The number of times the device has been reconnected.
+    pub(crate) fn reconnects(&self) -> u32 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+}
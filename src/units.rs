@@ -0,0 +1,464 @@
+//! Typed physical quantities.
+//!
+//! Each quantity is a thin wrapper around an [`f64`] stored in a fixed base
+//! unit, with constructors and accessors for the units commonly used on the V5.
+//! Quantities of the same kind can be added, subtracted and compared, and
+//! scaled by plain numbers; quantities of different kinds cannot be mixed up.
+
+use core::{
+    f64::consts::PI,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    time::Duration,
+};
+
+macro_rules! quantity {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+        pub struct $name(f64);
+
+        impl $name {
+            /// The zero quantity.
+            pub const ZERO: Self = Self(0.0);
+
+            #[inline]
+            /// Gets the absolute value of the quantity.
+            pub fn abs(self) -> Self {
+                Self(if self.0 < 0.0 { -self.0 } else { self.0 })
+            }
+
+            #[inline]
+            /// Restricts the quantity to the given range.
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                Self(self.0.clamp(min.0, max.0))
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<f64> for $name {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: f64) -> Self {
+                Self(self.0 * rhs)
+            }
+        }
+
+        impl Mul<$name> for f64 {
+            type Output = $name;
+
+            #[inline]
+            fn mul(self, rhs: $name) -> $name {
+                $name(self * rhs.0)
+            }
+        }
+
+        impl Div<f64> for $name {
+            type Output = Self;
+
+            #[inline]
+            fn div(self, rhs: f64) -> Self {
+                Self(self.0 / rhs)
+            }
+        }
+
+        impl Div for $name {
+            type Output = f64;
+
+            #[inline]
+            fn div(self, rhs: Self) -> f64 {
+                self.0 / rhs.0
+            }
+        }
+
+        impl AddAssign for $name {
+            #[inline]
+            fn add_assign(&mut self, rhs: Self) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl SubAssign for $name {
+            #[inline]
+            fn sub_assign(&mut self, rhs: Self) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl MulAssign<f64> for $name {
+            #[inline]
+            fn mul_assign(&mut self, rhs: f64) {
+                self.0 *= rhs;
+            }
+        }
+
+        impl DivAssign<f64> for $name {
+            #[inline]
+            fn div_assign(&mut self, rhs: f64) {
+                self.0 /= rhs;
+            }
+        }
+    };
+}
+
+quantity! {
+    /// An angle, or an angular position.
+    Angle
+}
+
+impl Angle {
+    #[inline]
+    /// Creates an angle from a number of radians.
+    pub fn from_radians(radians: f64) -> Self {
+        Self(radians)
+    }
+
+    #[inline]
+    /// Creates an angle from a number of degrees.
+    pub fn from_degrees(degrees: f64) -> Self {
+        Self(degrees * PI / 180.0)
+    }
+
+    #[inline]
+    /// Creates an angle from a number of hundredths of a degree.
+    pub fn from_centidegrees(centidegrees: f64) -> Self {
+        Self::from_degrees(centidegrees / 100.0)
+    }
+
+    #[inline]
+    /// Creates an angle from a number of full rotations.
+    pub fn from_rotations(rotations: f64) -> Self {
+        Self(rotations * 2.0 * PI)
+    }
+
+    #[inline]
+    /// Gets the angle in radians.
+    pub fn as_radians(self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    /// Gets the angle in degrees.
+    pub fn as_degrees(self) -> f64 {
+        self.0 * 180.0 / PI
+    }
+
+    #[inline]
+    /// Gets the angle in hundredths of a degree.
+    pub fn as_centidegrees(self) -> f64 {
+        self.as_degrees() * 100.0
+    }
+
+    #[inline]
+    /// Gets the angle in full rotations.
+    pub fn as_rotations(self) -> f64 {
+        self.0 / (2.0 * PI)
+    }
+}
+
+impl Div<Duration> for Angle {
+    type Output = AngularVelocity;
+
+    #[inline]
+    fn div(self, rhs: Duration) -> AngularVelocity {
+        AngularVelocity(self.0 / rhs.as_secs_f64())
+    }
+}
+
+quantity! {
+    /// An angular velocity.
+    AngularVelocity
+}
+
+impl AngularVelocity {
+    #[inline]
+    /// Creates an angular velocity from a number of radians per second.
+    pub fn from_radians_per_second(radians: f64) -> Self {
+        Self(radians)
+    }
+
+    #[inline]
+    /// Creates an angular velocity from a number of degrees per second.
+    pub fn from_degrees_per_second(degrees: f64) -> Self {
+        Self(Angle::from_degrees(degrees).0)
+    }
+
+    #[inline]
+    /// Creates an angular velocity from a number of hundredths of a degree per
+    /// second.
+    pub fn from_centidegrees_per_second(centidegrees: f64) -> Self {
+        Self(Angle::from_centidegrees(centidegrees).0)
+    }
+
+    #[inline]
+    /// Creates an angular velocity from a number of rotations per minute.
+    pub fn from_rpm(rpm: f64) -> Self {
+        Self(Angle::from_rotations(rpm).0 / 60.0)
+    }
+
+    #[inline]
+    /// Gets the angular velocity in radians per second.
+    pub fn as_radians_per_second(self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    /// Gets the angular velocity in degrees per second.
+    pub fn as_degrees_per_second(self) -> f64 {
+        Angle(self.0).as_degrees()
+    }
+
+    #[inline]
+    /// Gets the angular velocity in hundredths of a degree per second.
+    pub fn as_centidegrees_per_second(self) -> f64 {
+        Angle(self.0).as_centidegrees()
+    }
+
+    #[inline]
+    /// Gets the angular velocity in rotations per minute.
+    pub fn as_rpm(self) -> f64 {
+        Angle(self.0).as_rotations() * 60.0
+    }
+}
+
+impl Mul<Duration> for AngularVelocity {
+    type Output = Angle;
+
+    #[inline]
+    fn mul(self, rhs: Duration) -> Angle {
+        Angle(self.0 * rhs.as_secs_f64())
+    }
+}
+
+quantity! {
+    /// A length, or a linear distance.
+    Length
+}
+
+impl Length {
+    #[inline]
+    /// Creates a length from a number of metres.
+    pub fn from_meters(meters: f64) -> Self {
+        Self(meters)
+    }
+
+    #[inline]
+    /// Creates a length from a number of centimetres.
+    pub fn from_centimeters(centimeters: f64) -> Self {
+        Self(centimeters / 100.0)
+    }
+
+    #[inline]
+    /// Creates a length from a number of millimetres.
+    pub fn from_millimeters(millimeters: f64) -> Self {
+        Self(millimeters / 1000.0)
+    }
+
+    #[inline]
+    /// Creates a length from a number of inches.
+    pub fn from_inches(inches: f64) -> Self {
+        Self(inches * 0.0254)
+    }
+
+    #[inline]
+    /// Gets the length in metres.
+    pub fn as_meters(self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    /// Gets the length in centimetres.
+    pub fn as_centimeters(self) -> f64 {
+        self.0 * 100.0
+    }
+
+    #[inline]
+    /// Gets the length in millimetres.
+    pub fn as_millimeters(self) -> f64 {
+        self.0 * 1000.0
+    }
+
+    #[inline]
+    /// Gets the length in inches.
+    pub fn as_inches(self) -> f64 {
+        self.0 / 0.0254
+    }
+}
+
+impl Div<Duration> for Length {
+    type Output = Velocity;
+
+    #[inline]
+    fn div(self, rhs: Duration) -> Velocity {
+        Velocity(self.0 / rhs.as_secs_f64())
+    }
+}
+
+quantity! {
+    /// A linear velocity.
+    Velocity
+}
+
+impl Velocity {
+    #[inline]
+    /// Creates a velocity from a number of metres per second.
+    pub fn from_meters_per_second(meters: f64) -> Self {
+        Self(meters)
+    }
+
+    #[inline]
+    /// Creates a velocity from a number of inches per second.
+    pub fn from_inches_per_second(inches: f64) -> Self {
+        Self(Length::from_inches(inches).0)
+    }
+
+    #[inline]
+    /// Gets the velocity in metres per second.
+    pub fn as_meters_per_second(self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    /// Gets the velocity in inches per second.
+    pub fn as_inches_per_second(self) -> f64 {
+        Length(self.0).as_inches()
+    }
+}
+
+impl Mul<Duration> for Velocity {
+    type Output = Length;
+
+    #[inline]
+    fn mul(self, rhs: Duration) -> Length {
+        Length(self.0 * rhs.as_secs_f64())
+    }
+}
+
+quantity! {
+    /// An electric potential difference.
+    Voltage
+}
+
+impl Voltage {
+    #[inline]
+    /// Creates a voltage from a number of volts.
+    pub fn from_volts(volts: f64) -> Self {
+        Self(volts)
+    }
+
+    #[inline]
+    /// Creates a voltage from a number of millivolts.
+    pub fn from_millivolts(millivolts: f64) -> Self {
+        Self(millivolts / 1000.0)
+    }
+
+    #[inline]
+    /// Gets the voltage in volts.
+    pub fn as_volts(self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    /// Gets the voltage in millivolts.
+    pub fn as_millivolts(self) -> f64 {
+        self.0 * 1000.0
+    }
+}
+
+quantity! {
+    /// An electric current.
+    Current
+}
+
+impl Current {
+    #[inline]
+    /// Creates a current from a number of amperes.
+    pub fn from_amps(amps: f64) -> Self {
+        Self(amps)
+    }
+
+    #[inline]
+    /// Creates a current from a number of milliamperes.
+    pub fn from_milliamps(milliamps: f64) -> Self {
+        Self(milliamps / 1000.0)
+    }
+
+    #[inline]
+    /// Gets the current in amperes.
+    pub fn as_amps(self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    /// Gets the current in milliamperes.
+    pub fn as_milliamps(self) -> f64 {
+        self.0 * 1000.0
+    }
+}
+
+quantity! {
+    /// A temperature.
+    Temperature
+}
+
+impl Temperature {
+    #[inline]
+    /// Creates a temperature from a number of degrees Celsius.
+    pub fn from_celsius(celsius: f64) -> Self {
+        Self(celsius)
+    }
+
+    #[inline]
+    /// Creates a temperature from a number of degrees Fahrenheit.
+    pub fn from_fahrenheit(fahrenheit: f64) -> Self {
+        Self((fahrenheit - 32.0) * 5.0 / 9.0)
+    }
+
+    #[inline]
+    /// Gets the temperature in degrees Celsius.
+    pub fn as_celsius(self) -> f64 {
+        self.0
+    }
+
+    #[inline]
+    /// Gets the temperature in degrees Fahrenheit.
+    pub fn as_fahrenheit(self) -> f64 {
+        self.0 * 9.0 / 5.0 + 32.0
+    }
+}
+
+/// Rounds to the nearest integer, with halves rounded away from zero, since
+/// `f64::round()` is not available in `core`.
+pub(crate) fn round(x: f64) -> i32 {
+    if x < 0.0 {
+        (x - 0.5) as i32
+    } else {
+        (x + 0.5) as i32
+    }
+}